@@ -0,0 +1,180 @@
+pub mod answers {
+    use std::collections::BTreeMap;
+    use std::fmt;
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::config::config::Config;
+    use crate::error::error::AocError;
+    use crate::solution::solution::Answer;
+
+    const ANSWERS_FILE: &str = "answers.toml";
+
+    /// How a computed answer compares to the recorded one.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Verification {
+        Match,
+        Mismatch { expected: String },
+        Unrecorded,
+    }
+
+    impl fmt::Display for Verification {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Verification::Match => write!(f, "ok"),
+                Verification::Mismatch { expected } => write!(f, "MISMATCH, expected {}", expected),
+                Verification::Unrecorded => write!(f, "unrecorded"),
+            }
+        }
+    }
+
+    /// Known good answers for one year, stored as
+    ///
+    /// ```toml
+    /// [14]
+    /// 1 = "1003"
+    /// 2 = "25771"
+    /// ```
+    pub struct AnswerStore {
+        path: PathBuf,
+        answers: BTreeMap<usize, BTreeMap<u8, String>>,
+    }
+
+    impl AnswerStore {
+        pub fn load<P: Into<PathBuf>>(path: P) -> Result<AnswerStore, AocError> {
+            let path = path.into();
+            let mut answers = BTreeMap::new();
+
+            if path.exists() {
+                let text = fs::read_to_string(&path).map_err(|e| AocError::cache(&path, e))?;
+                let raw: BTreeMap<String, BTreeMap<String, String>> =
+                    toml::from_str(&text).map_err(|e| AocError::cache(&path, e))?;
+
+                for (day, parts) in raw {
+                    let day: usize = day
+                        .parse()
+                        .map_err(|_| AocError::cache(&path, format!("invalid day {:?}", day)))?;
+                    let mut entry = BTreeMap::new();
+                    for (part, answer) in parts {
+                        let part: u8 = part.parse().map_err(|_| {
+                            AocError::cache(
+                                &path,
+                                format!("invalid part {:?} for day {}", part, day),
+                            )
+                        })?;
+                        entry.insert(part, answer);
+                    }
+                    answers.insert(day, entry);
+                }
+            }
+
+            Ok(AnswerStore { path, answers })
+        }
+
+        /// `answers.toml` for `year` in the cache, usually
+        /// `.advent/{year}/answers.toml`
+        pub fn load_default(config: &Config, year: u16) -> Result<AnswerStore, AocError> {
+            AnswerStore::load(config.cache_dir().join(year.to_string()).join(ANSWERS_FILE))
+        }
+
+        pub fn save(&self) -> Result<(), AocError> {
+            let raw: BTreeMap<String, BTreeMap<String, &String>> = self
+                .answers
+                .iter()
+                .map(|(day, parts)| {
+                    let parts = parts.iter().map(|(p, a)| (p.to_string(), a)).collect();
+                    (day.to_string(), parts)
+                })
+                .collect();
+
+            if let Some(dir) = self.path.parent() {
+                fs::create_dir_all(dir).map_err(|e| AocError::cache(dir, e))?;
+            }
+            let text = toml::to_string(&raw).map_err(|e| AocError::cache(&self.path, e))?;
+            fs::write(&self.path, text).map_err(|e| AocError::cache(&self.path, e))
+        }
+
+        pub fn get(&self, day: usize, part: u8) -> Option<&String> {
+            self.answers.get(&day)?.get(&part)
+        }
+
+        pub fn record(&mut self, day: usize, part: u8, answer: &Answer) {
+            self.answers
+                .entry(day)
+                .or_default()
+                .insert(part, answer.to_string());
+        }
+
+        pub fn verify(&self, day: usize, part: u8, answer: &Answer) -> Verification {
+            match self.get(day, part) {
+                None => Verification::Unrecorded,
+                Some(expected) if *expected == answer.to_string() => Verification::Match,
+                Some(expected) => Verification::Mismatch {
+                    expected: expected.clone(),
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::answers::*;
+    use crate::error::error::AocError;
+    use crate::solution::solution::Answer;
+    use crate::testing::testing::temp_dir;
+
+    #[test]
+    fn verify_and_record() {
+        let path = temp_dir("answers").join("answers.toml");
+        let mut store = AnswerStore::load(&path).unwrap();
+
+        assert_eq!(
+            store.verify(14, 1, &Answer::Number(1003)),
+            Verification::Unrecorded
+        );
+
+        store.record(14, 1, &Answer::Number(1003));
+        store.record(10, 2, &Answer::Text("#..#\n.##.".to_string()));
+        store.save().unwrap();
+
+        let store = AnswerStore::load(&path).unwrap();
+        assert_eq!(
+            store.verify(14, 1, &Answer::Number(1003)),
+            Verification::Match
+        );
+        assert_eq!(
+            store.verify(14, 1, &Answer::Number(1004)),
+            Verification::Mismatch {
+                expected: "1003".to_string()
+            }
+        );
+        assert_eq!(
+            store.verify(10, 2, &Answer::Text("#..#\n.##.".to_string())),
+            Verification::Match
+        );
+    }
+
+    #[test]
+    fn reads_hand_written_files() {
+        let path = temp_dir("answers-manual").join("answers.toml");
+        fs::write(&path, "[5]\n1 = \"CMZ\"\n2 = \"MCD\"\n").unwrap();
+
+        let store = AnswerStore::load(&path).unwrap();
+        assert_eq!(store.get(5, 1).map(|s| s.as_str()), Some("CMZ"));
+        assert_eq!(store.get(5, 2).map(|s| s.as_str()), Some("MCD"));
+        assert_eq!(store.get(6, 1), None);
+    }
+
+    #[test]
+    fn rejects_bad_keys() {
+        let path = temp_dir("answers-bad").join("answers.toml");
+        fs::write(&path, "[five]\n1 = \"CMZ\"\n").unwrap();
+
+        let err = AnswerStore::load(&path).err().unwrap();
+        assert!(matches!(err, AocError::Cache { .. }));
+        assert!(err.to_string().contains("invalid day \"five\""), "{}", err);
+    }
+}
@@ -0,0 +1,317 @@
+pub mod bench {
+    use std::fmt::Write;
+    use std::process::Command;
+    use std::time::{Duration, Instant};
+
+    use serde_json::{json, Value};
+
+    use crate::error::error::AocError;
+    use crate::solution::solution::Solution;
+
+    /// Timings of repeated runs of the same step.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Stats {
+        pub min: Duration,
+        pub median: Duration,
+        pub max: Duration,
+    }
+
+    impl Stats {
+        pub fn from_samples(mut samples: Vec<Duration>) -> Option<Stats> {
+            if samples.is_empty() {
+                return None;
+            }
+            samples.sort();
+
+            let mid = samples.len() / 2;
+            let median = if samples.len().is_multiple_of(2) {
+                (samples[mid - 1] + samples[mid]) / 2
+            } else {
+                samples[mid]
+            };
+
+            Some(Stats {
+                min: samples[0],
+                median,
+                max: samples[samples.len() - 1],
+            })
+        }
+
+        fn to_json(self) -> Value {
+            json!({
+                "min_us": self.min.as_micros() as u64,
+                "median_us": self.median.as_micros() as u64,
+                "max_us": self.max.as_micros() as u64,
+            })
+        }
+    }
+
+    /// The timings for one day. `parse` is `None` for days without a
+    /// separate parse step.
+    #[derive(Debug, Clone)]
+    pub struct DayBench {
+        pub year: u16,
+        pub day: usize,
+        pub title: &'static str,
+        pub runs: usize,
+        pub parse: Option<Stats>,
+        pub part1: Stats,
+        pub part2: Stats,
+    }
+
+    fn time<T>(f: impl FnOnce() -> Result<T, AocError>) -> Result<Duration, AocError> {
+        let start = Instant::now();
+        f()?;
+        Ok(start.elapsed())
+    }
+
+    /// Runs each step of `solution` against `input` `runs` times.
+    pub fn bench(solution: &dyn Solution, input: &str, runs: usize) -> Result<DayBench, AocError> {
+        if runs == 0 {
+            return Err(AocError::config("need at least one run"));
+        }
+
+        let mut parse = vec![];
+        let mut part1 = vec![];
+        let mut part2 = vec![];
+
+        for _ in 0..runs {
+            let start = Instant::now();
+            if let Some(parsed) = solution.parse(input) {
+                parsed?;
+                parse.push(start.elapsed());
+            }
+
+            part1.push(time(|| solution.part1(input.to_string()))?);
+            part2.push(time(|| solution.part2(input.to_string()))?);
+        }
+
+        Ok(DayBench {
+            year: solution.year(),
+            day: solution.day(),
+            title: solution.title(),
+            runs,
+            parse: Stats::from_samples(parse),
+            part1: Stats::from_samples(part1).expect("at least one run"),
+            part2: Stats::from_samples(part2).expect("at least one run"),
+        })
+    }
+
+    pub fn format_duration(d: Duration) -> String {
+        let us = d.as_micros();
+        if us < 1_000 {
+            format!("{}µs", us)
+        } else if us < 1_000_000 {
+            format!("{:.2}ms", us as f64 / 1_000.0)
+        } else {
+            format!("{:.2}s", us as f64 / 1_000_000.0)
+        }
+    }
+
+    fn format_stats(stats: Option<Stats>) -> String {
+        match stats {
+            Some(s) => format!(
+                "{} / {} / {}",
+                format_duration(s.min),
+                format_duration(s.median),
+                format_duration(s.max)
+            ),
+            None => "-".to_string(),
+        }
+    }
+
+    fn rows(results: &[DayBench]) -> Vec<[String; 5]> {
+        results
+            .iter()
+            .map(|r| {
+                [
+                    format!("{:02}", r.day),
+                    r.title.to_string(),
+                    format_stats(r.parse),
+                    format_stats(Some(r.part1)),
+                    format_stats(Some(r.part2)),
+                ]
+            })
+            .collect()
+    }
+
+    const HEADER: [&str; 5] = [
+        "day",
+        "title",
+        "parse (min / median / max)",
+        "part 1",
+        "part 2",
+    ];
+
+    /// A plain text table with aligned columns.
+    pub fn table(results: &[DayBench]) -> String {
+        let rows = rows(results);
+        let mut widths = HEADER.map(|h| h.chars().count());
+        for row in &rows {
+            for (w, cell) in widths.iter_mut().zip(row) {
+                *w = (*w).max(cell.chars().count());
+            }
+        }
+
+        let mut out = String::new();
+        let header = HEADER.map(|h| h.to_string());
+        for row in std::iter::once(&header).chain(&rows) {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, w)| format!("{:<w$}", cell, w = w))
+                .collect();
+            writeln!(out, "{}", cells.join("  ").trim_end()).unwrap();
+        }
+        out
+    }
+
+    pub fn markdown(results: &[DayBench]) -> String {
+        let mut out = String::new();
+        writeln!(out, "| {} |", HEADER.join(" | ")).unwrap();
+        writeln!(out, "|{}", "---|".repeat(HEADER.len())).unwrap();
+        for row in rows(results) {
+            writeln!(out, "| {} |", row.join(" | ")).unwrap();
+        }
+        out
+    }
+
+    /// The current commit, so saved results can be compared across commits.
+    fn commit() -> Option<String> {
+        let output = Command::new("git")
+            .args(["rev-parse", "HEAD"])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(String::from_utf8(output.stdout).ok()?.trim().to_string())
+    }
+
+    pub fn json(results: &[DayBench]) -> String {
+        let days: Vec<Value> = results
+            .iter()
+            .map(|r| {
+                json!({
+                    "year": r.year,
+                    "day": r.day,
+                    "title": r.title,
+                    "runs": r.runs,
+                    "parse": r.parse.map(Stats::to_json),
+                    "part1": r.part1.to_json(),
+                    "part2": r.part2.to_json(),
+                })
+            })
+            .collect();
+
+        let report = json!({ "commit": commit(), "days": days });
+        serde_json::to_string_pretty(&report).expect("json values always serialize") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::bench::*;
+    use crate::error::error::AocError;
+    use crate::solution::solution::{Answer, Solution};
+
+    struct Fake;
+
+    impl Solution for Fake {
+        fn year(&self) -> u16 {
+            2022
+        }
+
+        fn day(&self) -> usize {
+            3
+        }
+
+        fn title(&self) -> &'static str {
+            "Fake"
+        }
+
+        fn part1(&self, text: String) -> Result<Answer, AocError> {
+            Ok(Answer::from(text.len()))
+        }
+
+        fn part2(&self, text: String) -> Result<Answer, AocError> {
+            text.parse::<i64>()
+                .map(Answer::Number)
+                .map_err(|e| AocError::parse(3, &text, &text, e.to_string()))
+        }
+    }
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn stats() {
+        assert_eq!(Stats::from_samples(vec![]), None);
+        assert_eq!(
+            Stats::from_samples(vec![ms(5), ms(1), ms(3)]),
+            Some(Stats {
+                min: ms(1),
+                median: ms(3),
+                max: ms(5)
+            })
+        );
+        assert_eq!(
+            Stats::from_samples(vec![ms(4), ms(1), ms(2), ms(9)]).map(|s| s.median),
+            Some(ms(3))
+        );
+    }
+
+    #[test]
+    fn bench_a_solution() {
+        let result = bench(&Fake, "42", 3).unwrap();
+        assert_eq!((result.day, result.runs), (3, 3));
+        assert!(result.parse.is_none());
+        assert!(result.part1.min <= result.part1.max);
+
+        assert!(matches!(
+            bench(&Fake, "forty-two", 3),
+            Err(AocError::Parse { day: 3, .. })
+        ));
+        assert!(matches!(
+            bench(&Fake, "42", 0),
+            Err(AocError::Config { .. })
+        ));
+    }
+
+    #[test]
+    fn reports() {
+        let stats = Stats {
+            min: Duration::from_micros(250),
+            median: ms(12),
+            max: Duration::from_secs(2),
+        };
+        let results = vec![DayBench {
+            year: 2022,
+            day: 3,
+            title: "Fake",
+            runs: 10,
+            parse: None,
+            part1: stats,
+            part2: stats,
+        }];
+
+        let table = table(&results);
+        assert!(table.starts_with("day  title  parse"));
+        assert!(table.contains("03   Fake   -"));
+        assert!(table.contains("250µs / 12.00ms / 2.00s"));
+
+        assert_eq!(
+            markdown(&results).lines().nth(2),
+            Some("| 03 | Fake | - | 250µs / 12.00ms / 2.00s | 250µs / 12.00ms / 2.00s |")
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&json(&results)).unwrap();
+        assert_eq!(json["days"][0]["year"], 2022);
+        assert_eq!(json["days"][0]["day"], 3);
+        assert_eq!(json["days"][0]["parse"], serde_json::Value::Null);
+        assert_eq!(json["days"][0]["part2"]["median_us"], 12_000);
+    }
+}
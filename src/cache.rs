@@ -0,0 +1,184 @@
+pub mod cache {
+    use std::fmt;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use serde::{Deserialize, Serialize};
+    use sha2::{Digest, Sha256};
+
+    /// Kept next to every cached file as `{file}.json`, so a cached input
+    /// can be checked against what was actually downloaded.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct Metadata {
+        /// seconds since the unix epoch
+        pub fetched_at: u64,
+        /// `None` when it didn't come from the site
+        pub url: Option<String>,
+        pub status: Option<u16>,
+        pub sha256: String,
+        pub profile: Option<String>,
+    }
+
+    impl Metadata {
+        pub fn new(text: &str, url: Option<String>, status: Option<u16>) -> Metadata {
+            Metadata {
+                fetched_at: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |d| d.as_secs()),
+                url,
+                status,
+                sha256: sha256(text),
+                profile: None,
+            }
+        }
+
+        pub fn with_profile(mut self, profile: Option<String>) -> Metadata {
+            self.profile = profile;
+            self
+        }
+    }
+
+    pub fn sha256(text: &str) -> String {
+        Sha256::digest(text.as_bytes())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Something wrong with a cached file.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Problem {
+        Empty,
+        /// an input that looks like a web page
+        Html,
+        /// the site's answer to a missing or expired session token
+        LoggedOut,
+        /// the file changed since it was downloaded, e.g. it was cut short
+        Changed {
+            expected: String,
+            actual: String,
+        },
+        /// the download didn't succeed but was kept anyway
+        Status(u16),
+        /// cached before metadata was kept, so nothing can be said about it
+        NoMetadata,
+    }
+
+    impl Problem {
+        /// Whether the file should be fetched again, rather than just
+        /// being of unknown origin.
+        pub fn is_suspicious(&self) -> bool {
+            !matches!(self, Problem::NoMetadata)
+        }
+    }
+
+    /// Enough of a hash to tell it apart, or all of it if it's shorter
+    /// than that (say the metadata was edited by hand).
+    fn short(hash: &str) -> &str {
+        hash.get(..12).unwrap_or(hash)
+    }
+
+    impl fmt::Display for Problem {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Problem::Empty => write!(f, "empty"),
+                Problem::Html => write!(f, "looks like a web page, not an input"),
+                Problem::LoggedOut => write!(f, "a \"please log in\" page"),
+                Problem::Changed { expected, actual } => write!(
+                    f,
+                    "changed since it was downloaded (sha256 {} instead of {})",
+                    short(actual),
+                    short(expected)
+                ),
+                Problem::Status(status) => write!(f, "downloaded with status {}", status),
+                Problem::NoMetadata => write!(f, "no metadata, cached before it was kept"),
+            }
+        }
+    }
+
+    /// Everything wrong with a cached `text`. `html` says whether it's
+    /// supposed to be a web page (a puzzle page) or not (an input).
+    pub fn problems(text: &str, metadata: Option<&Metadata>, html: bool) -> Vec<Problem> {
+        let mut problems = vec![];
+
+        if text.trim().is_empty() {
+            problems.push(Problem::Empty);
+        }
+        if text.contains("Puzzle inputs differ by user") || text.contains("Please log in") {
+            problems.push(Problem::LoggedOut);
+        }
+        let start = text.trim_start().to_ascii_lowercase();
+        if !html && (start.starts_with("<!doctype html") || start.starts_with("<html")) {
+            problems.push(Problem::Html);
+        }
+
+        match metadata {
+            Some(metadata) => {
+                let actual = sha256(text);
+                if actual != metadata.sha256 {
+                    problems.push(Problem::Changed {
+                        expected: metadata.sha256.clone(),
+                        actual,
+                    });
+                }
+                match metadata.status {
+                    Some(status) if !(200..300).contains(&status) => {
+                        problems.push(Problem::Status(status))
+                    }
+                    _ => {}
+                }
+            }
+            None => problems.push(Problem::NoMetadata),
+        }
+
+        problems
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::cache::*;
+
+    #[test]
+    fn hashes() {
+        assert_eq!(
+            sha256("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn finds_problems() {
+        let input = "1000\n2000\n";
+        let metadata = Metadata::new(input, None, Some(200));
+
+        assert!(problems(input, Some(&metadata), false).is_empty());
+        assert_eq!(problems(input, None, false), vec![Problem::NoMetadata]);
+        assert!(!Problem::NoMetadata.is_suspicious());
+
+        let truncated = problems("1000\n", Some(&metadata), false);
+        assert!(matches!(truncated[..], [Problem::Changed { .. }]));
+        assert!(truncated[0]
+            .to_string()
+            .contains(&format!("instead of {}", &sha256(input)[..12])));
+
+        let edited = Problem::Changed {
+            expected: "abc".to_string(),
+            actual: sha256(input),
+        };
+        assert!(edited.to_string().ends_with(" instead of abc)"));
+
+        let logged_out = "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+        assert!(problems(logged_out, None, false).contains(&Problem::LoggedOut));
+        assert!(problems("\n", None, false).contains(&Problem::Empty));
+
+        let page = "<!DOCTYPE html>\n<html><body>404</body></html>";
+        assert!(problems(page, None, false).contains(&Problem::Html));
+        assert!(!problems(page, None, true).contains(&Problem::Html));
+
+        let failed = Metadata::new(input, None, Some(500));
+        assert_eq!(
+            problems(input, Some(&failed), false),
+            vec![Problem::Status(500)]
+        );
+    }
+}
@@ -0,0 +1,100 @@
+pub mod cancel {
+    use std::cell::RefCell;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use crate::error::error::AocError;
+
+    /// Asks a solver to stop. The runner hands one to every part it gives a
+    /// time limit and cancels it once the time is up; solvers notice by
+    /// calling `check` in their long loops.
+    #[derive(Debug, Clone, Default)]
+    pub struct Token(Arc<AtomicBool>);
+
+    impl Token {
+        pub fn new() -> Token {
+            Token::default()
+        }
+
+        pub fn cancel(&self) {
+            self.0.store(true, Ordering::Relaxed);
+        }
+
+        pub fn is_cancelled(&self) -> bool {
+            self.0.load(Ordering::Relaxed)
+        }
+    }
+
+    thread_local! {
+        static CURRENT: RefCell<Option<Token>> = const { RefCell::new(None) };
+    }
+
+    /// Puts back whatever was current before, when dropped, so that holds
+    /// even if `f` panics.
+    struct Restore(Option<Token>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            CURRENT.with(|current| *current.borrow_mut() = previous);
+        }
+    }
+
+    /// Runs `f` with `token` as the one `check` looks at on this thread.
+    pub fn with_token<T>(token: &Token, f: impl FnOnce() -> T) -> T {
+        let _restore = Restore(CURRENT.with(|current| current.replace(Some(token.clone()))));
+        f()
+    }
+
+    /// Fails once the part running on this thread has been cancelled, so
+    /// `check(DAY)?` gets a solver out of a loop that would go on too long.
+    /// Outside the runner there's no token and this never fails.
+    pub fn check(day: usize) -> Result<(), AocError> {
+        let cancelled = CURRENT.with(|current| {
+            current
+                .borrow()
+                .as_ref()
+                .is_some_and(|token| token.is_cancelled())
+        });
+
+        if cancelled {
+            Err(AocError::cancelled(day))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::cancel::*;
+
+    #[test]
+    fn checks_the_current_token() {
+        assert!(check(1).is_ok());
+
+        let token = Token::new();
+        with_token(&token, || {
+            assert!(check(1).is_ok());
+            token.cancel();
+            assert_eq!(
+                check(1).unwrap_err().to_string(),
+                "day 1 was cancelled, it ran out of time"
+            );
+        });
+
+        // only the part that had the token is affected
+        assert!(check(1).is_ok());
+    }
+
+    #[test]
+    fn forgets_the_token_after_a_panic() {
+        let token = Token::new();
+        token.cancel();
+        let panicked =
+            std::panic::catch_unwind(|| with_token(&token, || panic!("the solver gave up")));
+        assert!(panicked.is_err());
+
+        assert!(check(1).is_ok());
+    }
+}
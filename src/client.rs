@@ -0,0 +1,297 @@
+pub mod client {
+    use std::sync::{Mutex, OnceLock};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use reqwest::blocking::{RequestBuilder, Response};
+    use reqwest::StatusCode;
+
+    use crate::config::config::Config;
+    use crate::error::error::AocError;
+
+    /// The least time between two requests to the site.
+    pub const MIN_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// How many times a `get` is repeated after a failure that might go
+    /// away by itself (a dropped connection, a 5xx, a 429).
+    pub const RETRIES: u32 = 3;
+
+    /// The wait before the first retry, doubling after that.
+    const BACKOFF: Duration = Duration::from_secs(1);
+
+    /// When the last request went out, shared by every client in the process
+    /// so the interval holds however many of them there are.
+    static LAST_REQUEST: Mutex<Option<Instant>> = Mutex::new(None);
+
+    /// The one way this crate talks to the site: identifies itself, keeps
+    /// to `MIN_INTERVAL`, retries what's worth retrying and only hands back
+    /// the body of a successful response.
+    pub struct Client {
+        user_agent: String,
+        /// built on the first request, most runs never make one
+        http: OnceLock<reqwest::blocking::Client>,
+        min_interval: Duration,
+        retries: u32,
+        backoff: Duration,
+    }
+
+    /// A successful response.
+    #[derive(Debug, Clone)]
+    pub struct Page {
+        pub url: String,
+        pub status: u16,
+        pub body: String,
+    }
+
+    /// `advent-2022/0.1.0 (you@example.com)`, with the contact from the
+    /// config if there is one.
+    pub fn user_agent(contact: Option<&str>) -> String {
+        let name = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+        match contact {
+            Some(contact) => format!("{} ({})", name, contact),
+            None => name.to_string(),
+        }
+    }
+
+    impl Client {
+        pub fn new(config: &Config) -> Client {
+            Client {
+                user_agent: user_agent(config.contact()),
+                http: OnceLock::new(),
+                min_interval: MIN_INTERVAL,
+                retries: RETRIES,
+                backoff: BACKOFF,
+            }
+        }
+
+        /// No throttle and no wait between retries, for talking to a
+        /// `MockServer`.
+        #[cfg(test)]
+        pub fn without_delays(mut self) -> Client {
+            self.min_interval = Duration::ZERO;
+            self.backoff = Duration::ZERO;
+            self
+        }
+
+        #[cfg(test)]
+        pub fn with_min_interval(mut self, min_interval: Duration) -> Client {
+            self.min_interval = min_interval;
+            self
+        }
+
+        fn http(&self) -> &reqwest::blocking::Client {
+            self.http.get_or_init(|| {
+                reqwest::blocking::Client::builder()
+                    .user_agent(&self.user_agent)
+                    .build()
+                    .expect("the http client has a valid configuration")
+            })
+        }
+
+        pub fn get(&self, url: &str, token: &str) -> Result<Page, AocError> {
+            self.send(url, true, || {
+                self.http().get(url).header("cookie", session(token))
+            })
+        }
+
+        pub fn post_form(
+            &self,
+            url: &str,
+            token: &str,
+            form: &[(&str, String)],
+        ) -> Result<Page, AocError> {
+            // an answer that got through before the failure counts as a
+            // guess, so a post is never repeated
+            self.send(url, false, || {
+                self.http()
+                    .post(url)
+                    .header("cookie", session(token))
+                    .form(form)
+            })
+        }
+
+        /// Waits until `min_interval` has passed since the last request. The
+        /// lock is held while waiting so concurrent callers queue up.
+        fn throttle(&self) {
+            let mut last = LAST_REQUEST.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(at) = *last {
+                let wait = self.min_interval.saturating_sub(at.elapsed());
+                if !wait.is_zero() {
+                    thread::sleep(wait);
+                }
+            }
+            *last = Some(Instant::now());
+        }
+
+        /// Sends what `request` builds, and with `retry` sends it again
+        /// after a failure that might go away by itself.
+        fn send(
+            &self,
+            url: &str,
+            retry: bool,
+            request: impl Fn() -> RequestBuilder,
+        ) -> Result<Page, AocError> {
+            let retries = if retry { self.retries } else { 0 };
+            let mut backoff = self.backoff;
+            let mut attempt = 0;
+
+            loop {
+                self.throttle();
+                let result = request().send().map_err(|e| AocError::network(url, e));
+
+                let transient = match &result {
+                    Ok(response) => is_transient(response.status()),
+                    Err(_) => true,
+                };
+                if transient && attempt < retries {
+                    attempt += 1;
+                    thread::sleep(backoff);
+                    backoff *= 2;
+                    continue;
+                }
+
+                return page(url, result?);
+            }
+        }
+    }
+
+    fn session(token: &str) -> String {
+        format!("session={}", token)
+    }
+
+    fn is_transient(status: StatusCode) -> bool {
+        status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+    }
+
+    /// The body of a successful response. Anything else is an error, so an
+    /// error page never ends up cached as if it was an input.
+    fn page(url: &str, response: Response) -> Result<Page, AocError> {
+        let status = response.status();
+        let text = response.text().map_err(|e| AocError::network(url, e))?;
+        if status.is_success() {
+            return Ok(Page {
+                url: url.to_string(),
+                status: status.as_u16(),
+                body: text,
+            });
+        }
+
+        let reason = text.lines().map(str::trim).find(|l| !l.is_empty());
+        let message = match (status, reason) {
+            (StatusCode::BAD_REQUEST, _) => format!(
+                "{}, the session token was probably rejected: {}",
+                status,
+                reason.unwrap_or("")
+            ),
+            (_, Some(reason)) => format!("{}: {}", status, reason),
+            (_, None) => status.to_string(),
+        };
+        Err(AocError::network(url, message))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::client::*;
+    use crate::config::config::Config;
+    use crate::testing::testing::MockServer;
+
+    fn client() -> Client {
+        Client::new(&Config::default()).without_delays()
+    }
+
+    #[test]
+    fn identifies_itself() {
+        assert_eq!(
+            user_agent(Some("me@example.com")),
+            format!("advent-2022/{} (me@example.com)", env!("CARGO_PKG_VERSION"))
+        );
+
+        let server = MockServer::start();
+        server.respond("/page", 200, "hello");
+
+        assert_eq!(
+            client()
+                .get(&format!("{}/page", server.url()), "secret")
+                .unwrap()
+                .body,
+            "hello"
+        );
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[0].header("user-agent"),
+            Some(user_agent(None).as_str())
+        );
+        assert_eq!(requests[0].header("cookie"), Some("session=secret"));
+    }
+
+    #[test]
+    fn rejects_error_pages() {
+        let server = MockServer::start();
+        server.respond(
+            "/2022/day/1/input",
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        );
+
+        let url = format!("{}/2022/day/1/input", server.url());
+        let err = client().get(&url, "expired").unwrap_err().to_string();
+        assert!(err.contains("400"), "{}", err);
+        assert!(err.contains("Please log in"), "{}", err);
+        // a client error won't go away by asking again
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn retries_server_errors() {
+        let server = MockServer::start();
+        server.respond("/flaky", 500, "oops");
+        server.respond("/flaky", 503, "busy");
+        server.respond("/flaky", 200, "fine");
+        server.respond("/down", 500, "oops");
+
+        let client = client();
+        assert_eq!(
+            client
+                .get(&format!("{}/flaky", server.url()), "secret")
+                .unwrap()
+                .body,
+            "fine"
+        );
+        assert_eq!(server.requests().len(), 3);
+
+        assert!(client
+            .get(&format!("{}/down", server.url()), "secret")
+            .is_err());
+        assert_eq!(server.requests().len(), 3 + 1 + RETRIES as usize);
+    }
+
+    #[test]
+    fn posts_only_once() {
+        let server = MockServer::start();
+        server.respond("/answer", 503, "busy");
+        server.respond("/answer", 200, "fine");
+
+        let url = format!("{}/answer", server.url());
+        let form = [("answer", "42".to_string())];
+        assert!(client().post_form(&url, "secret", &form).is_err());
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn keeps_its_distance() {
+        let server = MockServer::start();
+        server.respond("/page", 200, "hello");
+
+        let client = client().with_min_interval(Duration::from_millis(100));
+        let url = format!("{}/page", server.url());
+
+        let start = Instant::now();
+        client.get(&url, "secret").unwrap();
+        client.get(&url, "secret").unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+}
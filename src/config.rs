@@ -0,0 +1,407 @@
+pub mod config {
+    use std::collections::BTreeMap;
+    use std::env;
+    use std::fmt;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+
+    use serde::Deserialize;
+
+    use crate::error::error::AocError;
+    use crate::helper::helper::LOCAL_CACHE_DIR;
+
+    const CONFIG_FILE: &str = "advent.toml";
+    const SESSION_TOKEN_FILE: &str = ".advent";
+
+    /// Where a session token comes from.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum TokenSource {
+        /// A file holding just the token.
+        File(PathBuf),
+        /// An environment variable holding the token.
+        Env(String),
+        /// The token itself.
+        #[cfg(test)]
+        Value(String),
+    }
+
+    impl TokenSource {
+        pub fn read(&self) -> Result<String, AocError> {
+            let token = match self {
+                TokenSource::File(path) => {
+                    fs::read_to_string(path).map_err(|e| AocError::cache(path, e))?
+                }
+                TokenSource::Env(name) => {
+                    env::var(name).map_err(|e| AocError::config(format!("${}: {}", name, e)))?
+                }
+                #[cfg(test)]
+                TokenSource::Value(token) => token.clone(),
+            };
+
+            match token.trim() {
+                "" => Err(AocError::config("the token is empty")),
+                token => Ok(token.to_string()),
+            }
+        }
+    }
+
+    /// `~/.advent`
+    impl Default for TokenSource {
+        fn default() -> Self {
+            TokenSource::File(home().join(SESSION_TOKEN_FILE))
+        }
+    }
+
+    impl fmt::Display for TokenSource {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                TokenSource::File(path) => write!(f, "{}", path.display()),
+                TokenSource::Env(name) => write!(f, "${}", name),
+                #[cfg(test)]
+                TokenSource::Value(_) => write!(f, "a token given directly"),
+            }
+        }
+    }
+
+    fn home() -> PathBuf {
+        env::var_os("HOME").map(PathBuf::from).unwrap_or_default()
+    }
+
+    fn expand_home(path: PathBuf) -> PathBuf {
+        match path.strip_prefix("~") {
+            Ok(rest) => home().join(rest),
+            Err(_) => path,
+        }
+    }
+
+    #[derive(Debug, Default, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Profile {
+        cache_dir: Option<PathBuf>,
+        token_file: Option<PathBuf>,
+        token_env: Option<String>,
+    }
+
+    /// `advent.toml`, every setting is optional.
+    ///
+    /// ```toml
+    /// cache_dir = ".advent"
+    /// token_file = "~/.advent"
+    /// contact = "you@example.com"
+    /// timeout = 60
+    ///
+    /// [profiles.alice]
+    /// token_file = "~/.advent-alice"
+    ///
+    /// [profiles.bob]
+    /// token_env = "BOB_SESSION"
+    /// ```
+    #[derive(Debug, Default, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct ConfigFile {
+        cache_dir: Option<PathBuf>,
+        token_file: Option<PathBuf>,
+        token_env: Option<String>,
+        /// sent along with every request, so the site knows who to ask
+        /// about them
+        contact: Option<String>,
+        /// seconds `run` gives each part before giving up on it
+        timeout: Option<u64>,
+        #[serde(default)]
+        profiles: BTreeMap<String, Profile>,
+    }
+
+    fn parse_file(path: &Path, text: &str) -> Result<ConfigFile, AocError> {
+        toml::from_str(text).map_err(|e| AocError::config(format!("{}: {}", path.display(), e)))
+    }
+
+    fn token_source(
+        token_file: Option<PathBuf>,
+        token_env: Option<String>,
+    ) -> Result<Option<TokenSource>, AocError> {
+        match (token_file, token_env) {
+            (Some(_), Some(_)) => Err(AocError::config("set only one of token_file and token_env")),
+            (Some(file), None) => Ok(Some(TokenSource::File(expand_home(file)))),
+            (None, Some(name)) => Ok(Some(TokenSource::Env(name))),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Where inputs, answers and submissions are kept and which session
+    /// token to use, for the default account or one of the profiles.
+    #[derive(Debug, Clone)]
+    pub struct Config {
+        root: PathBuf,
+        cache_dir: PathBuf,
+        profile: Option<String>,
+        profiles: Vec<String>,
+        token: TokenSource,
+        contact: Option<String>,
+        timeout: Option<Duration>,
+    }
+
+    /// `.advent` and `~/.advent`, as if there was no config at all.
+    impl Default for Config {
+        fn default() -> Self {
+            Config {
+                root: PathBuf::from(LOCAL_CACHE_DIR),
+                cache_dir: PathBuf::from(LOCAL_CACHE_DIR),
+                profile: None,
+                profiles: vec![],
+                token: TokenSource::default(),
+                contact: None,
+                timeout: None,
+            }
+        }
+    }
+
+    impl Config {
+        /// Reads `$ADVENT_CONFIG`, or `advent.toml` if there is one, and the
+        /// `ADVENT_*` environment variables. `profile` wins over
+        /// `$ADVENT_PROFILE`.
+        pub fn load(profile: Option<&str>) -> Result<Config, AocError> {
+            let (path, required) = match env::var_os("ADVENT_CONFIG") {
+                Some(path) => (PathBuf::from(path), true),
+                None => (PathBuf::from(CONFIG_FILE), false),
+            };
+
+            let file = if required || path.exists() {
+                let text = fs::read_to_string(&path).map_err(|e| {
+                    AocError::config(format!("unable to read {}: {}", path.display(), e))
+                })?;
+                parse_file(&path, &text)?
+            } else {
+                ConfigFile::default()
+            };
+
+            Config::resolve(file, |name| env::var(name).ok(), profile)
+        }
+
+        /// Settings from the environment win over the file, and a profile
+        /// only uses its own token.
+        fn resolve<F: Fn(&str) -> Option<String>>(
+            file: ConfigFile,
+            env: F,
+            profile: Option<&str>,
+        ) -> Result<Config, AocError> {
+            let root = env("ADVENT_CACHE_DIR")
+                .map(PathBuf::from)
+                .or(file.cache_dir)
+                .map_or_else(|| PathBuf::from(LOCAL_CACHE_DIR), expand_home);
+
+            let contact = env("ADVENT_CONTACT").or(file.contact);
+            let timeout = match env("ADVENT_TIMEOUT") {
+                Some(secs) => Some(secs.parse().map_err(|_| {
+                    AocError::config(format!(
+                        "ADVENT_TIMEOUT should be a number of seconds, not {:?}",
+                        secs
+                    ))
+                })?),
+                None => file.timeout,
+            };
+            let profiles = file.profiles.keys().cloned().collect();
+            let profile = profile
+                .map(|p| p.to_string())
+                .or_else(|| env("ADVENT_PROFILE"));
+
+            let (cache_dir, token) = match &profile {
+                Some(name) => {
+                    let p = file.profiles.get(name).ok_or_else(|| {
+                        AocError::config(format!("there is no profile {:?} in the config", name))
+                    })?;
+                    let token = token_source(p.token_file.clone(), p.token_env.clone())?
+                        .ok_or_else(|| {
+                            AocError::config(format!(
+                                "profile {:?} needs a token_file or token_env",
+                                name
+                            ))
+                        })?;
+                    let cache_dir = p
+                        .cache_dir
+                        .clone()
+                        .map_or_else(|| root.join("profiles").join(name), expand_home);
+                    (cache_dir, token)
+                }
+                None => {
+                    let token = match env("ADVENT_SESSION") {
+                        Some(_) => TokenSource::Env("ADVENT_SESSION".to_string()),
+                        None => token_source(file.token_file, file.token_env)?.unwrap_or_default(),
+                    };
+                    (root.clone(), token)
+                }
+            };
+
+            Ok(Config {
+                root,
+                cache_dir,
+                profile,
+                profiles,
+                token,
+                contact,
+                timeout: timeout.filter(|&secs| secs > 0).map(Duration::from_secs),
+            })
+        }
+
+        /// The shared cache root, where hand-made inputs live.
+        pub fn root(&self) -> &Path {
+            &self.root
+        }
+
+        /// Where this account's inputs, answers and submissions are cached.
+        pub fn cache_dir(&self) -> &Path {
+            &self.cache_dir
+        }
+
+        pub fn profile(&self) -> Option<&str> {
+            self.profile.as_deref()
+        }
+
+        pub fn profiles(&self) -> &[String] {
+            &self.profiles
+        }
+
+        pub fn token(&self) -> &TokenSource {
+            &self.token
+        }
+
+        /// How to get in touch about this tool's requests, for the
+        /// User-Agent.
+        pub fn contact(&self) -> Option<&str> {
+            self.contact.as_deref()
+        }
+
+        /// How long `run` gives each part, unless `--timeout` says
+        /// otherwise. `None` means no limit, which is what a timeout of 0
+        /// in the config or `ADVENT_TIMEOUT` turns into.
+        pub fn timeout(&self) -> Option<Duration> {
+            self.timeout
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use std::collections::HashMap;
+        use std::path::{Path, PathBuf};
+
+        use super::*;
+
+        fn config(
+            file: &str,
+            env: &[(&str, &str)],
+            profile: Option<&str>,
+        ) -> Result<Config, AocError> {
+            let env: HashMap<String, String> = env
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            let file = parse_file(Path::new(CONFIG_FILE), file)?;
+
+            Config::resolve(file, |name| env.get(name).cloned(), profile)
+        }
+
+        const FILE: &str = r#"
+    cache_dir = "cache"
+    token_env = "AOC_TOKEN"
+    contact = "team@example.com"
+    timeout = 60
+
+    [profiles.alice]
+    token_file = "/tokens/alice"
+
+    [profiles.bob]
+    token_env = "BOB_SESSION"
+    cache_dir = "/shared/bob"
+    "#;
+
+        #[test]
+        fn defaults() {
+            let c = config("", &[], None).unwrap();
+
+            assert_eq!(c.cache_dir(), Path::new(".advent"));
+            assert_eq!(c.root(), Path::new(".advent"));
+            assert!(matches!(c.token(), TokenSource::File(p) if p.ends_with(".advent")));
+            assert_eq!(c.profile(), None);
+        }
+
+        #[test]
+        fn from_file() {
+            let c = config(FILE, &[], None).unwrap();
+
+            assert_eq!(c.cache_dir(), Path::new("cache"));
+            assert_eq!(c.token(), &TokenSource::Env("AOC_TOKEN".to_string()));
+            assert_eq!(c.profiles(), ["alice", "bob"]);
+            assert_eq!(c.contact(), Some("team@example.com"));
+            assert_eq!(c.timeout(), Some(Duration::from_secs(60)));
+        }
+
+        #[test]
+        fn environment_wins() {
+            let env = [
+                ("ADVENT_CACHE_DIR", "/tmp/aoc"),
+                ("ADVENT_SESSION", "abc"),
+                ("ADVENT_CONTACT", "me@example.com"),
+                ("ADVENT_TIMEOUT", "0"),
+            ];
+            let c = config(FILE, &env, None).unwrap();
+
+            assert_eq!(c.cache_dir(), Path::new("/tmp/aoc"));
+            assert_eq!(c.contact(), Some("me@example.com"));
+            assert_eq!(c.token(), &TokenSource::Env("ADVENT_SESSION".to_string()));
+            assert_eq!(c.timeout(), None);
+
+            let err = config(FILE, &[("ADVENT_TIMEOUT", "soon")], None).unwrap_err();
+            assert!(matches!(err, AocError::Config { .. }));
+            assert!(err.to_string().contains("ADVENT_TIMEOUT"), "{}", err);
+        }
+
+        #[test]
+        fn profiles() {
+            let alice = config(FILE, &[("ADVENT_SESSION", "abc")], Some("alice")).unwrap();
+            assert_eq!(alice.profile(), Some("alice"));
+            assert_eq!(alice.cache_dir(), Path::new("cache/profiles/alice"));
+            assert_eq!(alice.root(), Path::new("cache"));
+            assert_eq!(
+                alice.token(),
+                &TokenSource::File(PathBuf::from("/tokens/alice"))
+            );
+
+            let bob = config(FILE, &[("ADVENT_PROFILE", "bob")], None).unwrap();
+            assert_eq!(bob.cache_dir(), Path::new("/shared/bob"));
+            assert_eq!(bob.token(), &TokenSource::Env("BOB_SESSION".to_string()));
+
+            // the flag wins over the environment
+            let c = config(FILE, &[("ADVENT_PROFILE", "bob")], Some("alice")).unwrap();
+            assert_eq!(c.profile(), Some("alice"));
+        }
+
+        #[test]
+        fn bad_configs() {
+            let is_config = |r| matches!(r, Err(AocError::Config { .. }));
+            assert!(is_config(config(FILE, &[], Some("carol"))));
+            assert!(is_config(config("[profiles.carol]\n", &[], Some("carol"))));
+            assert!(is_config(config(
+                "token_file = \"a\"\ntoken_env = \"B\"\n",
+                &[],
+                None
+            )));
+            assert!(is_config(config("cache = \"typo\"\n", &[], None)));
+        }
+
+        #[test]
+        fn token_sources() {
+            assert_eq!(
+                TokenSource::Value(" secret\n".to_string()).read().unwrap(),
+                "secret"
+            );
+            assert!(matches!(
+                TokenSource::Value("\n".to_string()).read(),
+                Err(AocError::Config { .. })
+            ));
+            assert!(matches!(
+                TokenSource::File(PathBuf::from("/does/not/exist")).read(),
+                Err(AocError::Cache { .. })
+            ));
+        }
+    }
+}
@@ -1,7 +1,12 @@
-#[cfg(test)]
 pub mod day01 {
     use itertools::Itertools;
 
+    use crate::error::error::AocError;
+    use crate::solution::solution::{Answer, Solution};
+
+    const YEAR: u16 = 2022;
+    const DAY: usize = 1;
+
     #[derive(Debug)]
     struct Elf {
         calories: Vec<isize>,
@@ -9,7 +14,7 @@ pub mod day01 {
 
     impl Elf {
         fn new() -> Elf {
-            return Elf { calories: vec![] };
+            Elf { calories: vec![] }
         }
 
         fn add_calorie(&mut self, calorie: isize) {
@@ -21,40 +26,48 @@ pub mod day01 {
         }
     }
 
-    pub fn part1(text: String) -> Result<isize, Box<dyn std::error::Error>> {
+    pub fn part1(text: String) -> Result<isize, AocError> {
         let mut elves: Vec<Elf> = vec![];
         let mut current_elf = Elf::new();
 
         for line in text.lines() {
-            if line == "" {
+            if line.is_empty() {
                 elves.push(current_elf);
                 current_elf = Elf::new();
                 continue;
             }
 
-            let calorie: Result<isize, _> = line.parse();
-            current_elf.add_calorie(calorie?);
+            let calorie = line
+                .parse::<isize>()
+                .map_err(|e| AocError::parse(DAY, &text, line, e.to_string()))?;
+            current_elf.add_calorie(calorie);
         }
+        // the input doesn't end with a blank line, so the last elf is still open
+        elves.push(current_elf);
 
         let result = elves.iter().map(|e| e.total()).max();
 
-        result.ok_or_else(|| "no sum found".into())
+        result.ok_or_else(|| AocError::no_solution(DAY, "no sum found"))
     }
 
-    pub fn part2(text: String) -> Result<isize, Box<dyn std::error::Error>> {
+    pub fn part2(text: String) -> Result<isize, AocError> {
         let mut elves: Vec<Elf> = vec![];
         let mut current_elf = Elf::new();
 
         for line in text.lines() {
-            if line == "" {
+            if line.is_empty() {
                 elves.push(current_elf);
                 current_elf = Elf::new();
                 continue;
             }
 
-            let calorie: Result<isize, _> = line.parse();
-            current_elf.add_calorie(calorie?);
+            let calorie = line
+                .parse::<isize>()
+                .map_err(|e| AocError::parse(DAY, &text, line, e.to_string()))?;
+            current_elf.add_calorie(calorie);
         }
+        // the input doesn't end with a blank line, so the last elf is still open
+        elves.push(current_elf);
 
         let result: isize = elves
             .iter()
@@ -65,6 +78,30 @@ pub mod day01 {
 
         Ok(result)
     }
+
+    pub struct Day01;
+
+    impl Solution for Day01 {
+        fn year(&self) -> u16 {
+            YEAR
+        }
+
+        fn day(&self) -> usize {
+            DAY
+        }
+
+        fn title(&self) -> &'static str {
+            "Calorie Counting"
+        }
+
+        fn part1(&self, text: String) -> Result<Answer, AocError> {
+            part1(text).map(Answer::from)
+        }
+
+        fn part2(&self, text: String) -> Result<Answer, AocError> {
+            part2(text).map(Answer::from)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -72,15 +109,16 @@ mod test {
     use super::day01;
     use crate::helper::helper::run_day;
 
+    const YEAR: u16 = 2022;
     const DAY: usize = 1;
 
     #[test]
     fn part1() {
-        run_day(DAY, day01::part1);
+        run_day(YEAR, DAY, 1, day01::part1);
     }
 
     #[test]
     fn part2() {
-        run_day(DAY, day01::part2);
+        run_day(YEAR, DAY, 2, day01::part2);
     }
 }
@@ -1,7 +1,12 @@
-#[cfg(test)]
 pub mod day02 {
     use std::fmt::Debug;
 
+    use crate::error::error::AocError;
+    use crate::solution::solution::{Answer, Solution};
+
+    const YEAR: u16 = 2022;
+    const DAY: usize = 2;
+
     #[derive(Debug)]
     struct RPSLine {
         theirs: RPS,
@@ -63,6 +68,7 @@ pub mod day02 {
         }
     }
 
+    #[allow(clippy::upper_case_acronyms)]
     #[derive(Debug, PartialEq, Copy, Clone)]
     enum RPS {
         Rock,
@@ -152,17 +158,47 @@ pub mod day02 {
         }
     }
 
-    pub fn part1(text: String) -> Result<isize, Box<dyn std::error::Error>> {
-        let matches: Result<Vec<RPSLine>, _> = text.lines().map(|line| line.try_into()).collect();
+    pub fn part1(text: String) -> Result<isize, AocError> {
+        let matches: Result<Vec<RPSLine>, _> = text
+            .lines()
+            .map(|line| RPSLine::try_from(line).map_err(|e| AocError::parse(DAY, &text, line, e)))
+            .collect();
 
         Ok(matches?.iter().map(|m| m.score()).sum())
     }
 
-    pub fn part2(text: String) -> Result<isize, Box<dyn std::error::Error>> {
-        let matches: Result<Vec<RPSLine2>, _> = text.lines().map(|line| line.try_into()).collect();
+    pub fn part2(text: String) -> Result<isize, AocError> {
+        let matches: Result<Vec<RPSLine2>, _> = text
+            .lines()
+            .map(|line| RPSLine2::try_from(line).map_err(|e| AocError::parse(DAY, &text, line, e)))
+            .collect();
 
         Ok(matches?.iter().map(|m| m.score()).sum())
     }
+
+    pub struct Day02;
+
+    impl Solution for Day02 {
+        fn year(&self) -> u16 {
+            YEAR
+        }
+
+        fn day(&self) -> usize {
+            DAY
+        }
+
+        fn title(&self) -> &'static str {
+            "Rock Paper Scissors"
+        }
+
+        fn part1(&self, text: String) -> Result<Answer, AocError> {
+            part1(text).map(Answer::from)
+        }
+
+        fn part2(&self, text: String) -> Result<Answer, AocError> {
+            part2(text).map(Answer::from)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -171,15 +207,16 @@ mod test {
 
     use super::day02::{self};
 
+    const YEAR: u16 = 2022;
     const DAY: usize = 2;
 
     #[test]
     fn part1() {
-        run_day(DAY, day02::part1);
+        run_day(YEAR, DAY, 1, day02::part1);
     }
 
     #[test]
     fn part2() {
-        run_day(DAY, day02::part2);
+        run_day(YEAR, DAY, 2, day02::part2);
     }
 }
@@ -1,14 +1,19 @@
-#[cfg(test)]
 pub mod day03 {
     use std::collections::HashSet;
 
     use itertools::Itertools;
 
+    use crate::error::error::AocError;
+    use crate::solution::solution::{Answer, Solution};
+
+    const YEAR: u16 = 2022;
+    const DAY: usize = 3;
+
     pub fn priority(c: char) -> u8 {
-        if 'a' <= c && c <= 'z' {
-            (c as u8) - ('a' as u8) + 1
-        } else if 'A' <= c && c <= 'Z' {
-            (c as u8) - ('A' as u8) + 1 + 26
+        if c.is_ascii_lowercase() {
+            (c as u8) - b'a' + 1
+        } else if c.is_ascii_uppercase() {
+            (c as u8) - b'A' + 1 + 26
         } else {
             0
         }
@@ -27,7 +32,7 @@ pub mod day03 {
         result
     }
 
-    pub fn part1(text: String) -> Result<i32, Box<dyn std::error::Error>> {
+    pub fn part1(text: String) -> Result<i32, AocError> {
         let result: i32 = text
             .lines()
             .map(split_into_parts)
@@ -42,25 +47,50 @@ pub mod day03 {
         Ok(result)
     }
 
-    pub fn part2(text: String) -> Result<i32, Box<dyn std::error::Error>> {
-        let result: i32 = text
+    pub fn part2(text: String) -> Result<i32, AocError> {
+        let result = text
             .lines()
-            .map(|bag| create_set_of_chars(bag))
+            .map(create_set_of_chars)
             .chunks(3)
             .into_iter()
             .map(|mut bags| {
-                let a = bags.next().unwrap();
-                let b = bags.next().unwrap();
-                let c = bags.next().unwrap();
+                let (a, b, c) = match (bags.next(), bags.next(), bags.next()) {
+                    (Some(a), Some(b), Some(c)) => (a, b, c),
+                    _ => return Err(AocError::invalid_input(DAY, "elves come in groups of 3")),
+                };
 
-                let interim: HashSet<char> = a.intersection(&b).map(|&c| c).collect();
+                let interim: HashSet<char> = a.intersection(&b).copied().collect();
                 let result = c.intersection(&interim);
-                result.map(|&c| priority(c) as i32).sum::<i32>()
+                Ok(result.map(|&c| priority(c) as i32).sum::<i32>())
             })
-            .sum();
+            .sum::<Result<i32, AocError>>()?;
 
         Ok(result)
     }
+
+    pub struct Day03;
+
+    impl Solution for Day03 {
+        fn year(&self) -> u16 {
+            YEAR
+        }
+
+        fn day(&self) -> usize {
+            DAY
+        }
+
+        fn title(&self) -> &'static str {
+            "Rucksack Reorganization"
+        }
+
+        fn part1(&self, text: String) -> Result<Answer, AocError> {
+            part1(text).map(Answer::from)
+        }
+
+        fn part2(&self, text: String) -> Result<Answer, AocError> {
+            part2(text).map(Answer::from)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -71,6 +101,7 @@ mod test {
 
     use super::day03::{self, priority, split_into_parts};
 
+    const YEAR: u16 = 2022;
     const DAY: usize = 3;
 
     #[test]
@@ -105,11 +136,11 @@ mod test {
 
     #[test]
     fn part1() {
-        run_day(DAY, day03::part1);
+        run_day(YEAR, DAY, 1, day03::part1);
     }
 
     #[test]
     fn part2() {
-        run_day(DAY, day03::part2);
+        run_day(YEAR, DAY, 2, day03::part2);
     }
 }
@@ -1,6 +1,10 @@
-#[cfg(test)]
 pub mod day04 {
-    use std::collections::{hash_map::RandomState, HashSet};
+    use crate::error::error::AocError;
+    use crate::interval::interval::IntervalSet;
+    use crate::solution::solution::{Answer, Solution};
+
+    const YEAR: u16 = 2022;
+    const DAY: usize = 4;
 
     pub struct Section {
         start: isize,
@@ -8,29 +12,34 @@ pub mod day04 {
     }
 
     impl Section {
+        #[cfg(test)]
         pub fn new(start: isize, end: isize) -> Section {
-            Section {
-                start: start,
-                end: end,
-            }
+            Section { start, end }
         }
 
         fn fully_contains(&self, b: &Section) -> bool {
-            self.start <= b.start && self.end >= b.end
+            b.to_set().difference(&self.to_set()).is_empty()
         }
 
-        pub fn to_seq(&self) -> Vec<isize> {
-            (self.start..=self.end).collect()
+        pub fn to_set(&self) -> IntervalSet<isize> {
+            IntervalSet::from(self.start..=self.end)
         }
     }
 
-    fn parse_section(s: &str) -> Section {
-        let mut parts = s.split("-");
+    fn parse_number(text: &str, n: &str) -> Result<isize, AocError> {
+        n.parse()
+            .map_err(|e: std::num::ParseIntError| AocError::parse(DAY, text, n, e.to_string()))
+    }
 
-        Section {
-            start: parts.next().unwrap().parse().unwrap(),
-            end: parts.next().unwrap().parse().unwrap(),
-        }
+    fn parse_section(text: &str, s: &str) -> Result<Section, AocError> {
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| AocError::parse(DAY, text, s, "expected a range like 2-4"))?;
+
+        Ok(Section {
+            start: parse_number(text, start)?,
+            end: parse_number(text, end)?,
+        })
     }
 
     struct Row {
@@ -38,49 +47,71 @@ pub mod day04 {
         b: Section,
     }
 
-    fn parse_row(s: &str) -> Row {
-        let parts: Vec<&str> = s.split(",").collect();
+    fn parse_row(text: &str, s: &str) -> Result<Row, AocError> {
+        let (a, b) = s
+            .split_once(',')
+            .ok_or_else(|| AocError::parse(DAY, text, s, "expected two ranges"))?;
 
-        Row {
-            a: parse_section(parts[0]),
-            b: parse_section(parts[1]),
-        }
+        Ok(Row {
+            a: parse_section(text, a)?,
+            b: parse_section(text, b)?,
+        })
     }
 
-    pub fn part1(text: String) -> Result<usize, Box<dyn std::error::Error>> {
-        let lines = text.lines();
+    fn parse_rows(text: &str) -> Result<Vec<Row>, AocError> {
+        text.lines().map(|line| parse_row(text, line)).collect()
+    }
 
-        let result = lines
-            .map(parse_row)
+    pub fn part1(text: String) -> Result<usize, AocError> {
+        let result = parse_rows(&text)?
+            .into_iter()
             .filter(|row| {
                 let a = &row.a;
                 let b = &row.b;
 
-                a.fully_contains(&b) || b.fully_contains(&a)
+                a.fully_contains(b) || b.fully_contains(a)
             })
             .count();
 
         Ok(result)
     }
 
-    pub fn part2(text: String) -> Result<usize, Box<dyn std::error::Error>> {
-        let lines = text.lines();
-
-        let rows = lines
-            .map(parse_row)
-            .map(|row| {
-                let row_a = row.a.to_seq();
-                let row_b = row.b.to_seq();
-                let a: HashSet<&isize, RandomState> = HashSet::from_iter(row_a.iter());
-                let b: HashSet<&isize, RandomState> = HashSet::from_iter(row_b.iter());
-
-                a.intersection(&b).count()
-            })
-            .filter(|&count| count > 0)
+    pub fn part2(text: String) -> Result<usize, AocError> {
+        let rows = parse_rows(&text)?
+            .into_iter()
+            .filter(|row| !row.a.to_set().intersection(&row.b.to_set()).is_empty())
             .count();
 
         Ok(rows)
     }
+
+    pub struct Day04;
+
+    impl Solution for Day04 {
+        fn year(&self) -> u16 {
+            YEAR
+        }
+
+        fn day(&self) -> usize {
+            DAY
+        }
+
+        fn title(&self) -> &'static str {
+            "Camp Cleanup"
+        }
+
+        fn part1(&self, text: String) -> Result<Answer, AocError> {
+            part1(text).map(Answer::from)
+        }
+
+        fn part2(&self, text: String) -> Result<Answer, AocError> {
+            part2(text).map(Answer::from)
+        }
+
+        fn parse(&self, text: &str) -> Option<Result<(), AocError>> {
+            Some(parse_rows(text).map(|_| ()))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -89,21 +120,25 @@ mod test {
 
     use super::day04;
 
+    const YEAR: u16 = 2022;
     const DAY: usize = 4;
 
     #[test]
     fn row_overlap() {
         let a = day04::Section::new(1, 5);
-        assert_eq!(a.to_seq(), vec!(1, 2, 3, 4, 5));
+        assert_eq!(a.to_set().len(), 5);
+
+        let b = day04::Section::new(5, 9);
+        assert_eq!(a.to_set().intersection(&b.to_set()).len(), 1);
     }
 
     #[test]
     fn part1() {
-        run_day(DAY, day04::part1);
+        run_day(YEAR, DAY, 1, day04::part1);
     }
 
     #[test]
     fn part2() {
-        run_day(DAY, day04::part2);
+        run_day(YEAR, DAY, 2, day04::part2);
     }
 }
@@ -1,5 +1,10 @@
-#[cfg(test)]
 pub mod day05 {
+    use crate::error::error::AocError;
+    use crate::solution::solution::{Answer, Solution};
+
+    const YEAR: u16 = 2022;
+    const DAY: usize = 5;
+
     fn create_boxes(boxes_str: &str) -> Vec<Vec<char>> {
         let boxes: Vec<Vec<char>> = boxes_str.lines().map(|s| s.chars().collect()).collect();
 
@@ -11,7 +16,8 @@ pub mod day05 {
         while index < line_length {
             let mut v = vec![];
             for line in &boxes[0..boxes.len() - 1] {
-                let c = line[index];
+                // short lines just have nothing on the stacks past their end
+                let c = line.get(index).copied().unwrap_or(' ');
                 if c != ' ' {
                     // insert in front because we are loading them in backwards from the text
                     // and we want to use pop later
@@ -32,83 +38,139 @@ pub mod day05 {
         dest: usize,
     }
 
-    fn parse_instruction(line: &str) -> Result<Instruction, Box<dyn std::error::Error>> {
+    fn parse_instruction(text: &str, line: &str) -> Result<Instruction, AocError> {
         let tokens: Vec<&str> = line.split_whitespace().collect();
 
+        let number = |i: usize| {
+            let token = tokens
+                .get(i)
+                .ok_or_else(|| AocError::parse(DAY, text, line, "expected move N from A to B"))?;
+            token.parse().map_err(|e: std::num::ParseIntError| {
+                AocError::parse(DAY, text, token, e.to_string())
+            })
+        };
+
         Ok(Instruction {
-            count: tokens[1].parse()?,
-            src: tokens[3].parse()?,
-            dest: tokens[5].parse()?,
+            count: number(1)?,
+            src: number(3)?,
+            dest: number(5)?,
         })
     }
 
-    fn get_boxes_and_instructions(
-        text: String,
-    ) -> Result<(Vec<Vec<char>>, Vec<Instruction>), Box<dyn std::error::Error>> {
-        let mut texts = text.split("\n\n");
-        let boxes_str = texts.next().unwrap();
-        let instructions_str = texts.next().unwrap();
+    type Stacks = Vec<Vec<char>>;
+
+    fn get_boxes_and_instructions(text: &str) -> Result<(Stacks, Vec<Instruction>), AocError> {
+        let (boxes_str, instructions_str) = text.split_once("\n\n").ok_or_else(|| {
+            AocError::invalid_input(
+                DAY,
+                "expected a blank line between the stacks and the moves",
+            )
+        })?;
 
         let stacks = create_boxes(boxes_str);
-        let instructions: Result<Vec<Instruction>, _> =
-            instructions_str.lines().map(parse_instruction).collect();
+        let instructions: Result<Vec<Instruction>, _> = instructions_str
+            .lines()
+            .map(|line| parse_instruction(text, line))
+            .collect();
 
-        Ok((stacks, instructions.map_err(|e| e.to_string())?))
+        Ok((stacks, instructions?))
     }
 
-    pub fn part1(text: String) -> Result<String, Box<dyn std::error::Error>> {
-        let (mut stacks, instructions) = get_boxes_and_instructions(text)?;
+    /// The 1-based stack `n`.
+    fn stack(stacks: &mut Stacks, n: usize) -> Result<&mut Vec<char>, AocError> {
+        n.checked_sub(1)
+            .and_then(|i| stacks.get_mut(i))
+            .ok_or_else(|| AocError::invalid_input(DAY, format!("there is no stack {}", n)))
+    }
+
+    fn take(stacks: &mut Stacks, n: usize) -> Result<char, AocError> {
+        stack(stacks, n)?
+            .pop()
+            .ok_or_else(|| AocError::invalid_input(DAY, format!("stack {} is already empty", n)))
+    }
+
+    pub fn part1(text: String) -> Result<String, AocError> {
+        let (mut stacks, instructions) = get_boxes_and_instructions(&text)?;
 
         for inst in instructions {
             for _ in 0..inst.count {
-                let item = stacks[inst.src - 1].pop().unwrap();
-                stacks[inst.dest - 1].push(item);
+                let item = take(&mut stacks, inst.src)?;
+                stack(&mut stacks, inst.dest)?.push(item);
             }
         }
 
-        let result: String = stacks.iter().map(|s| s.last().unwrap()).collect();
+        let result: String = stacks.iter().flat_map(|s| s.last()).collect();
 
         Ok(result)
     }
 
-    pub fn part2(text: String) -> Result<String, Box<dyn std::error::Error>> {
-        let (mut stacks, instructions) = get_boxes_and_instructions(text)?;
+    pub fn part2(text: String) -> Result<String, AocError> {
+        let (mut stacks, instructions) = get_boxes_and_instructions(&text)?;
 
         for inst in instructions {
             let mut boxes = vec![];
             for _ in 0..inst.count {
-                let item = stacks[inst.src - 1].pop().unwrap();
+                let item = take(&mut stacks, inst.src)?;
                 boxes.insert(0, item);
             }
-            stacks[inst.dest - 1].append(&mut boxes);
+            stack(&mut stacks, inst.dest)?.append(&mut boxes);
         }
 
-        let result: String = stacks.iter().map(|s| s.last().unwrap()).collect();
+        let result: String = stacks.iter().flat_map(|s| s.last()).collect();
 
         Ok(result)
     }
+
+    pub struct Day05;
+
+    impl Solution for Day05 {
+        fn year(&self) -> u16 {
+            YEAR
+        }
+
+        fn day(&self) -> usize {
+            DAY
+        }
+
+        fn title(&self) -> &'static str {
+            "Supply Stacks"
+        }
+
+        fn part1(&self, text: String) -> Result<Answer, AocError> {
+            part1(text).map(Answer::from)
+        }
+
+        fn part2(&self, text: String) -> Result<Answer, AocError> {
+            part2(text).map(Answer::from)
+        }
+
+        fn parse(&self, text: &str) -> Option<Result<(), AocError>> {
+            Some(get_boxes_and_instructions(text).map(|_| ()))
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::helper::helper::run_day;
+    use crate::helper::helper::{run_day, show_input};
 
     use super::day05;
 
+    const YEAR: u16 = 2022;
     const DAY: usize = 5;
 
     #[test]
     fn dataset() {
-        run_day(DAY, |text| Ok(text));
+        show_input(YEAR, DAY);
     }
 
     #[test]
     fn part1() {
-        run_day(DAY, day05::part1);
+        run_day(YEAR, DAY, 1, day05::part1);
     }
 
     #[test]
     fn part2() {
-        run_day(DAY, day05::part2);
+        run_day(YEAR, DAY, 2, day05::part2);
     }
 }
@@ -1,7 +1,12 @@
-#[cfg(test)]
 pub mod day06 {
     use std::collections::HashSet;
 
+    use crate::error::error::AocError;
+    use crate::solution::solution::{Answer, Solution};
+
+    const YEAR: u16 = 2022;
+    const DAY: usize = 6;
+
     pub fn all_unique(s: &str) -> bool {
         let chars: HashSet<char> = s.chars().collect();
         chars.len() == s.len()
@@ -19,16 +24,40 @@ pub mod day06 {
         None
     }
 
-    pub fn part1(text: String) -> Result<usize, Box<dyn std::error::Error>> {
+    pub fn part1(text: String) -> Result<usize, AocError> {
         let page_size = 4;
         let answer = find_packet_character_count(text, page_size);
-        answer.ok_or("no answer found".into())
+        answer.ok_or_else(|| AocError::no_solution(DAY, "no marker found"))
     }
 
-    pub fn part2(text: String) -> Result<usize, Box<(dyn std::error::Error + 'static)>> {
+    pub fn part2(text: String) -> Result<usize, AocError> {
         let page_size = 14;
         let answer = find_packet_character_count(text, page_size);
-        answer.ok_or("no answer found".into())
+        answer.ok_or_else(|| AocError::no_solution(DAY, "no marker found"))
+    }
+
+    pub struct Day06;
+
+    impl Solution for Day06 {
+        fn year(&self) -> u16 {
+            YEAR
+        }
+
+        fn day(&self) -> usize {
+            DAY
+        }
+
+        fn title(&self) -> &'static str {
+            "Tuning Trouble"
+        }
+
+        fn part1(&self, text: String) -> Result<Answer, AocError> {
+            part1(text).map(Answer::from)
+        }
+
+        fn part2(&self, text: String) -> Result<Answer, AocError> {
+            part2(text).map(Answer::from)
+        }
     }
 }
 
@@ -38,6 +67,7 @@ mod test {
 
     use super::day06;
 
+    const YEAR: u16 = 2022;
     const DAY: usize = 6;
 
     #[test]
@@ -51,11 +81,11 @@ mod test {
 
     #[test]
     fn part1() {
-        run_day(DAY, day06::part1);
+        run_day(YEAR, DAY, 1, day06::part1);
     }
 
     #[test]
     fn part2() {
-        run_day(DAY, day06::part2);
+        run_day(YEAR, DAY, 2, day06::part2);
     }
 }
@@ -1,11 +1,15 @@
-#[cfg(test)]
 pub mod day07 {
     use itertools::Itertools;
 
+    use crate::error::error::AocError;
+    use crate::solution::solution::{Answer, Solution};
     use crate::tree::tree::Tree;
 
     use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
+    const YEAR: u16 = 2022;
+    const DAY: usize = 7;
+
     #[derive(Debug)]
     struct Data<S>
     where
@@ -20,10 +24,7 @@ pub mod day07 {
         S: Into<String>,
     {
         pub fn new(name: S) -> Data<S> {
-            Data {
-                name: name,
-                size: 0,
-            }
+            Data { name, size: 0 }
         }
     }
 
@@ -50,7 +51,8 @@ pub mod day07 {
         result
     }
 
-    fn find_file_sizes(lines: Vec<&str>) -> HashMap<String, usize> {
+    fn find_file_sizes(text: &str) -> Result<HashMap<String, usize>, AocError> {
+        let lines: Vec<&str> = text.lines().collect();
         let mut cursor = 0;
 
         let root = Tree::new(Data::new("/"));
@@ -59,8 +61,7 @@ pub mod day07 {
         while cursor < lines.len() {
             let line = lines[cursor];
 
-            if line.starts_with("$ cd") {
-                let directory_name = line.split_whitespace().last().unwrap();
+            if let Some(directory_name) = line.strip_prefix("$ cd ") {
                 if directory_name == "/" {
                     current = Rc::clone(&root);
                 } else if directory_name == ".." {
@@ -83,29 +84,35 @@ pub mod day07 {
                         break;
                     }
 
-                    let value = next_line.split_whitespace().next().unwrap();
+                    let value = next_line.split_whitespace().next().ok_or_else(|| {
+                        AocError::parse(DAY, text, next_line, "expected a file or directory")
+                    })?;
 
-                    if let Ok(size) = value.parse::<usize>() {
+                    // directories are listed as `dir a`, and only counted once we cd into them
+                    if value != "dir" {
+                        let size: usize = value.parse().map_err(|_| {
+                            AocError::parse(DAY, text, value, "expected a file size or dir")
+                        })?;
                         current.as_ref().borrow_mut().data.size += size;
                     }
 
                     cursor += 1;
                 }
             } else {
-                panic!("something unhandled this way comes: {}", line);
+                return Err(AocError::parse(DAY, text, line, "expected $ cd or $ ls"));
             }
         }
 
         let mut data: HashMap<String, usize> = HashMap::new();
         sum_children_stored(&root, vec![], &mut data);
 
-        data
+        Ok(data)
     }
 
     const MAX_SIZE: usize = 100000;
 
-    pub fn part1(text: String) -> Result<usize, Box<dyn std::error::Error>> {
-        let data = find_file_sizes(text.lines().collect());
+    pub fn part1(text: String) -> Result<usize, AocError> {
+        let data = find_file_sizes(&text)?;
 
         let result = data
             .iter()
@@ -120,17 +127,19 @@ pub mod day07 {
     const TOTAL_FILESYSTEM_SIZE: usize = 70000000;
     const FREE_SPACE_NEEDED: usize = 30000000;
 
-    pub fn part2(text: String) -> Result<usize, Box<dyn std::error::Error>> {
-        let data = find_file_sizes(text.lines().collect());
+    pub fn part2(text: String) -> Result<usize, AocError> {
+        let data = find_file_sizes(&text)?;
 
         let max = data
             .iter()
             .map(|(_, &i)| i)
             .max()
-            .ok_or("no max value found")?;
+            .ok_or_else(|| AocError::no_solution(DAY, "no directories found"))?;
 
-        let current_free_space = TOTAL_FILESYSTEM_SIZE - max;
-        let needed_free_space = FREE_SPACE_NEEDED - current_free_space;
+        let current_free_space = TOTAL_FILESYSTEM_SIZE
+            .checked_sub(max)
+            .ok_or_else(|| AocError::invalid_input(DAY, "the files don't fit on the disk"))?;
+        let needed_free_space = FREE_SPACE_NEEDED.saturating_sub(current_free_space);
 
         let result = data
             .iter()
@@ -138,10 +147,34 @@ pub mod day07 {
             .filter(|&i| i >= needed_free_space)
             .sorted()
             .next()
-            .ok_or("no answer matches criteria".into());
+            .ok_or_else(|| AocError::no_solution(DAY, "no directory is big enough"));
 
         result
     }
+
+    pub struct Day07;
+
+    impl Solution for Day07 {
+        fn year(&self) -> u16 {
+            YEAR
+        }
+
+        fn day(&self) -> usize {
+            DAY
+        }
+
+        fn title(&self) -> &'static str {
+            "No Space Left On Device"
+        }
+
+        fn part1(&self, text: String) -> Result<Answer, AocError> {
+            part1(text).map(Answer::from)
+        }
+
+        fn part2(&self, text: String) -> Result<Answer, AocError> {
+            part2(text).map(Answer::from)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -150,15 +183,16 @@ mod test {
 
     use super::day07;
 
+    const YEAR: u16 = 2022;
     const DAY: usize = 7;
 
     #[test]
     fn part1() {
-        run_day(DAY, day07::part1);
+        run_day(YEAR, DAY, 1, day07::part1);
     }
 
     #[test]
     fn part2() {
-        run_day(DAY, day07::part2);
+        run_day(YEAR, DAY, 2, day07::part2);
     }
 }
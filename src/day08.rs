@@ -1,136 +1,112 @@
-#[cfg(test)]
 pub mod day08 {
-    use std::fmt::Debug;
+    use crate::error::error::AocError;
+    use crate::grid::grid::{Grid, Pos};
+    use crate::point::point::Direction;
+    use crate::solution::solution::{Answer, Solution};
 
-    #[derive(Debug)]
-    pub struct Grid<T: Debug + PartialOrd> {
-        cells: Vec<Vec<T>>,
-    }
+    const YEAR: u16 = 2022;
+    const DAY: usize = 8;
 
-    impl<T: Debug + PartialOrd> Grid<T> {
-        pub fn get<'a>(&'a self, x: usize, y: usize) -> Option<&'a T> {
-            self.cells.get(y)?.get(x)
-        }
+    type Tree = i32;
 
-        pub fn height(&self) -> usize {
-            self.cells.len()
-        }
+    pub fn hidden(grid: &Grid<Tree>, pos: Pos) -> bool {
+        let value = grid[pos];
 
-        pub fn width(&self) -> usize {
-            self.cells[0].len()
-        }
+        Direction::ALL
+            .iter()
+            .all(|d| grid.ray(pos, d.step()).any(|p| grid[p] >= value))
+    }
 
-        pub fn north<'a>(&'a self, x: usize, y: usize) -> impl Iterator<Item = &'a T> {
-            (0..y).rev().flat_map(move |i| self.get(x, i))
-        }
+    // count until we see one that is our height or higher
+    // we can't use take_while, because we still want to count the last one
+    // if it's larger than the first
+    fn viewing_distance(grid: &Grid<Tree>, pos: Pos, direction: Direction) -> usize {
+        let my_height = grid[pos];
+        let mut result = 0;
 
-        pub fn south<'a>(&'a self, x: usize, y: usize) -> impl Iterator<Item = &'a T> {
-            (y + 1..self.height()).flat_map(move |i| self.get(x, i))
-        }
+        for p in grid.ray(pos, direction.step()) {
+            result += 1;
 
-        pub fn east<'a>(&'a self, x: usize, y: usize) -> impl Iterator<Item = &'a T> {
-            (x + 1..self.width()).flat_map(move |i| self.get(i, y))
-        }
-
-        pub fn west<'a>(&'a self, x: usize, y: usize) -> impl Iterator<Item = &'a T> {
-            (0..x).rev().flat_map(move |i| self.get(i, y))
+            if my_height <= grid[p] {
+                break;
+            }
         }
 
-        pub fn hidden(&self, x: usize, y: usize) -> bool {
-            let value = self.get(x, y).unwrap();
-
-            self.north(x, y).any(|i| i >= value)
-                && self.south(x, y).any(|i| i >= value)
-                && self.east(x, y).any(|i| i >= value)
-                && self.west(x, y).any(|i| i >= value)
-        }
+        result
+    }
 
-        // take until we see one that is our height or higher
-        // we can't use take_while, because we still want the last one
-        // if it's larger than the first
-        fn take_until<'a, U: 'a + PartialOrd>(
-            iter: &mut impl Iterator<Item = &'a U>,
-            my_height: &U,
-        ) -> Vec<&'a U> {
-            let mut result = vec![];
-
-            for i in iter {
-                result.push(i);
-
-                if my_height <= i {
-                    break;
-                }
-            }
+    pub fn scenic_score(grid: &Grid<Tree>, pos: Pos) -> usize {
+        Direction::ALL
+            .iter()
+            .map(|&d| viewing_distance(grid, pos, d))
+            .product()
+    }
 
-            result
-        }
+    pub fn new_from_string<S: Into<String>>(text: S) -> Result<Grid<Tree>, AocError> {
+        let text = text.into();
+        Grid::parse(DAY, &text, "expected a digit", |c| {
+            c.to_digit(10).map(|d| d as Tree)
+        })
+    }
 
-        pub fn scenic_score(&self, x: usize, y: usize) -> usize {
-            let value = self.get(x, y).unwrap();
+    pub fn part1(text: String) -> Result<isize, AocError> {
+        let grid = new_from_string(text)?;
 
-            let north_line: Vec<&T> = Grid::<T>::take_until(&mut self.north(x, y), value);
-            let south_line: Vec<&T> = Grid::<T>::take_until(&mut self.south(x, y), value);
-            let east_line: Vec<&T> = Grid::<T>::take_until(&mut self.east(x, y), value);
-            let west_line: Vec<&T> = Grid::<T>::take_until(&mut self.west(x, y), value);
+        let count = grid.positions().filter(|&pos| !hidden(&grid, pos)).count();
 
-            north_line.len() * south_line.len() * east_line.len() * west_line.len()
-        }
+        Ok(count as isize)
     }
 
-    type Tree = i32;
-
-    pub fn new_from_string<S: Into<String>>(
-        text: S,
-    ) -> Result<Grid<Tree>, Box<dyn std::error::Error>> {
-        let cells: Result<Vec<Vec<Tree>>, _> = text
-            .into()
-            .lines()
-            .map(|line| line.chars().map(|c| c.to_string().parse()).collect())
-            .collect();
+    pub fn part2(text: String) -> Result<usize, AocError> {
+        let grid = new_from_string(text)?;
 
-        let grid = Grid { cells: cells? };
+        let max = grid
+            .positions()
+            .map(|pos| scenic_score(&grid, pos))
+            .max()
+            .unwrap_or(0);
 
-        Ok(grid)
+        Ok(max)
     }
 
-    pub fn part1(text: String) -> Result<isize, Box<dyn std::error::Error>> {
-        let grid = new_from_string(text)?;
+    pub struct Day08;
 
-        let mut count = 0;
-        for y in 0..grid.height() {
-            for x in 0..grid.width() {
-                if !grid.hidden(x, y) {
-                    count += 1;
-                }
-            }
+    impl Solution for Day08 {
+        fn year(&self) -> u16 {
+            YEAR
         }
 
-        Ok(count)
-    }
+        fn day(&self) -> usize {
+            DAY
+        }
 
-    pub fn part2(text: String) -> Result<usize, Box<dyn std::error::Error>> {
-        let grid = new_from_string(text)?;
+        fn title(&self) -> &'static str {
+            "Treetop Tree House"
+        }
 
-        let mut max = 0;
-        for y in 0..grid.height() {
-            for x in 0..grid.width() {
-                let score = grid.scenic_score(x, y);
-                if score > max {
-                    max = score;
-                }
-            }
+        fn part1(&self, text: String) -> Result<Answer, AocError> {
+            part1(text).map(Answer::from)
         }
 
-        Ok(max)
+        fn part2(&self, text: String) -> Result<Answer, AocError> {
+            part2(text).map(Answer::from)
+        }
+
+        fn parse(&self, text: &str) -> Option<Result<(), AocError>> {
+            Some(new_from_string(text).map(|_| ()))
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
+    use crate::debug;
     use crate::helper::helper::run_day;
+    use crate::point::point::Point;
 
-    use super::day08::{self, new_from_string};
+    use super::day08::{self, hidden, new_from_string, scenic_score};
 
+    const YEAR: u16 = 2022;
     const DAY: usize = 8;
 
     fn example() -> impl Into<String> {
@@ -151,7 +127,7 @@ mod test {
         ];
 
         for ((x, y), expected) in tests {
-            let actual = grid.hidden(x, y);
+            let actual = hidden(&grid, Point::new(x, y));
             assert_eq!(
                 !actual, expected,
                 "expected ({},{}) to be {}",
@@ -167,19 +143,19 @@ mod test {
         let tests = [((0, 0), 0), ((2, 1), 4), ((2, 3), 8)];
 
         for ((x, y), expected) in tests {
-            let actual = grid.scenic_score(x, y);
-            println!("{:?}", grid.get(x, y));
+            let actual = scenic_score(&grid, Point::new(x, y));
+            debug!("{:?}", grid.get(Point::new(x, y)));
             assert_eq!(actual, expected, "wrong score for ({},{})", x, y);
         }
     }
 
     #[test]
     fn part1() {
-        run_day(DAY, day08::part1);
+        run_day(YEAR, DAY, 1, day08::part1);
     }
 
     #[test]
     fn part2() {
-        run_day(DAY, day08::part2);
+        run_day(YEAR, DAY, 2, day08::part2);
     }
 }
@@ -1,177 +1,128 @@
-#[cfg(test)]
 pub mod day09 {
-    use std::{collections::HashSet, str::FromStr};
+    use crate::error::error::AocError;
+    use crate::point::point::{Direction, Point};
+    use crate::solution::solution::{Answer, Solution};
+    use crate::sparse::sparse::SparseGrid;
+
+    const YEAR: u16 = 2022;
+    const DAY: usize = 9;
 
     #[derive(Debug)]
-    enum Direction {
-        Up(usize),
-        Down(usize),
-        Left(usize),
-        Right(usize),
+    struct Move {
+        direction: Direction,
+        count: usize,
     }
 
-    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-    struct Coord {
-        x: isize,
-        y: isize,
-    }
+    type Knot = Point<isize>;
 
-    fn execute_move(
-        dir: &Direction,
-        head: &mut Coord,
-        tail: &mut Coord,
-        tail_positions: &mut HashSet<Coord>,
-    ) {
-        match dir {
-            Direction::Up(count) => {
-                for _ in 0..*count {
-                    head.y += 1;
-                    move_tail(head, tail);
-                    tail_positions.insert(tail.clone());
-                }
-            }
-            Direction::Down(count) => {
-                for _ in 0..*count {
-                    head.y -= 1;
-                    move_tail(head, tail);
-                    tail_positions.insert(tail.clone());
-                }
-            }
-            Direction::Left(count) => {
-                for _ in 0..*count {
-                    head.x -= 1;
-                    move_tail(head, tail);
-                    tail_positions.insert(tail.clone());
-                }
-            }
-            Direction::Right(count) => {
-                for _ in 0..*count {
-                    head.x += 1;
-                    move_tail(head, tail);
-                    tail_positions.insert(tail.clone());
-                }
-            }
+    /// Everywhere the tail has been.
+    #[derive(Default)]
+    struct Visited(SparseGrid<bool>);
+
+    impl Visited {
+        fn visit(&mut self, knot: Knot) {
+            self.0.set(knot, true);
         }
     }
 
-    fn execute_move_chain(
-        dir: &Direction,
-        snake: &mut Vec<Coord>,
-        tail_positions: &mut HashSet<Coord>,
-    ) {
-        match dir {
-            Direction::Up(count) => {
-                for _ in 0..*count {
-                    snake[0].y += 1;
-                    for i in 0..snake.len() - 1 {
-                        move_tail(&snake[i].clone(), &mut snake[i + 1])
-                    }
-                    tail_positions.insert(snake[snake.len() - 1].clone());
-                }
-            }
-            Direction::Down(count) => {
-                for _ in 0..*count {
-                    snake[0].y -= 1;
-                    for i in 0..snake.len() - 1 {
-                        move_tail(&snake[i].clone(), &mut snake[i + 1])
-                    }
-                    tail_positions.insert(snake[snake.len() - 1].clone());
-                }
-            }
-            Direction::Left(count) => {
-                for _ in 0..*count {
-                    snake[0].x -= 1;
-                    for i in 0..snake.len() - 1 {
-                        move_tail(&snake[i].clone(), &mut snake[i + 1])
-                    }
-                    tail_positions.insert(snake[snake.len() - 1].clone());
-                }
-            }
-            Direction::Right(count) => {
-                for _ in 0..*count {
-                    snake[0].x += 1;
-                    for i in 0..snake.len() - 1 {
-                        move_tail(&snake[i].clone(), &mut snake[i + 1])
-                    }
-                    tail_positions.insert(snake[snake.len() - 1].clone());
-                }
+    fn execute_move_chain(m: &Move, snake: &mut [Knot], tail_positions: &mut Visited) {
+        for _ in 0..m.count {
+            snake[0] += m.direction.step();
+            for i in 1..snake.len() {
+                move_tail(snake[i - 1], &mut snake[i]);
             }
+            tail_positions.visit(snake[snake.len() - 1]);
         }
     }
 
-    fn move_tail(head: &Coord, tail: &mut Coord) {
-        if head.x != tail.x && head.y != tail.y {
-            let diff_x = head.x - tail.x;
-            let diff_y = head.y - tail.y;
-
-            if diff_x.abs() >= 2 || diff_y.abs() >= 2 {
-                tail.x += if diff_x < 0 { -1 } else { 1 };
-                tail.y += if diff_y < 0 { -1 } else { 1 };
-            }
-        } else if head.x != tail.x {
-            let diff = head.x - tail.x;
-            if diff.abs() >= 2 {
-                tail.x += if diff < 0 { -1 } else { 1 }
-            }
-        } else if head.y != tail.y {
-            let diff = head.y - tail.y;
-            if diff.abs() >= 2 {
-                tail.y += if diff < 0 { -1 } else { 1 }
-            }
+    /// A knot only moves once it's no longer touching the one ahead, and
+    /// then goes one step straight or diagonally towards it.
+    fn move_tail(head: Knot, tail: &mut Knot) {
+        if head.chebyshev(*tail) >= 2 {
+            *tail += (head - *tail).signum();
         }
     }
 
-    impl FromStr for Direction {
-        type Err = Box<dyn std::error::Error>;
+    fn parse_move(text: &str, line: &str) -> Result<Move, AocError> {
+        let (dir, num) = line.split_once(' ').ok_or_else(|| {
+            AocError::parse(DAY, text, line, "expected a direction and an amount")
+        })?;
 
-        fn from_str(s: &str) -> Result<Self, Self::Err> {
-            let mut parts = s.split_whitespace();
+        let num = num
+            .parse()
+            .map_err(|e: std::num::ParseIntError| AocError::parse(DAY, text, num, e.to_string()))?;
 
-            let dir = parts.next().ok_or("missing direction")?;
-            let num = parts.next().ok_or("missing amount")?;
+        let direction = match dir {
+            "U" => Direction::North,
+            "D" => Direction::South,
+            "L" => Direction::West,
+            "R" => Direction::East,
 
-            let result = match dir {
-                "U" => Direction::Up(num.parse()?),
-                "D" => Direction::Down(num.parse()?),
-                "L" => Direction::Left(num.parse()?),
-                "R" => Direction::Right(num.parse()?),
+            _ => return Err(AocError::parse(DAY, text, dir, "expected U, D, L or R")),
+        };
 
-                _ => Err("invalid direction")?,
-            };
+        Ok(Move {
+            direction,
+            count: num,
+        })
+    }
 
-            Ok(result)
-        }
+    fn parse_moves(text: &str) -> Result<Vec<Move>, AocError> {
+        text.lines().map(|line| parse_move(text, line)).collect()
     }
 
-    pub fn part1(text: String) -> Result<usize, Box<dyn std::error::Error>> {
-        let dirs: Result<Vec<Direction>, _> = text.lines().map(|c| c.parse()).collect();
+    pub fn part1(text: String) -> Result<usize, AocError> {
+        let moves = parse_moves(&text)?;
+
+        let mut snake = [Point::ORIGIN; 2];
+        let mut tail_positions = Visited::default();
+
+        for m in moves {
+            execute_move_chain(&m, &mut snake, &mut tail_positions);
+        }
+
+        Ok(tail_positions.0.len())
+    }
 
-        let start = Coord { x: 0, y: 0 };
-        let mut head = start.clone();
-        let mut tail = start.clone();
+    pub fn part2(text: String) -> Result<usize, AocError> {
+        let moves = parse_moves(&text)?;
 
-        let mut tail_positions = HashSet::new();
+        let mut snake = [Point::ORIGIN; 10];
+        let mut tail_positions = Visited::default();
 
-        for dir in dirs? {
-            execute_move(&dir, &mut head, &mut tail, &mut tail_positions);
+        for m in moves {
+            execute_move_chain(&m, &mut snake, &mut tail_positions);
         }
 
-        Ok(tail_positions.len())
+        Ok(tail_positions.0.len())
     }
 
-    pub fn part2(text: String) -> Result<usize, Box<dyn std::error::Error>> {
-        let dirs: Result<Vec<Direction>, _> = text.lines().map(|c| c.parse()).collect();
+    pub struct Day09;
 
-        let start = Coord { x: 0, y: 0 };
-        let mut snake: Vec<Coord> = (0..10).map(|_| start.clone()).collect();
+    impl Solution for Day09 {
+        fn year(&self) -> u16 {
+            YEAR
+        }
+
+        fn day(&self) -> usize {
+            DAY
+        }
 
-        let mut tail_positions = HashSet::new();
+        fn title(&self) -> &'static str {
+            "Rope Bridge"
+        }
 
-        for dir in dirs? {
-            execute_move_chain(&dir, &mut snake, &mut tail_positions);
+        fn part1(&self, text: String) -> Result<Answer, AocError> {
+            part1(text).map(Answer::from)
         }
 
-        Ok(tail_positions.len())
+        fn part2(&self, text: String) -> Result<Answer, AocError> {
+            part2(text).map(Answer::from)
+        }
+
+        fn parse(&self, text: &str) -> Option<Result<(), AocError>> {
+            Some(parse_moves(text).map(|_| ()))
+        }
     }
 }
 
@@ -181,15 +132,16 @@ mod test {
 
     use super::day09;
 
+    const YEAR: u16 = 2022;
     const DAY: usize = 9;
 
     #[test]
     fn part1() {
-        run_day(DAY, day09::part1);
+        run_day(YEAR, DAY, 1, day09::part1);
     }
 
     #[test]
     fn part2() {
-        run_day(DAY, day09::part2);
+        run_day(YEAR, DAY, 2, day09::part2);
     }
 }
@@ -1,9 +1,14 @@
-#[cfg(test)]
 pub mod day10 {
-    use std::{collections::HashMap, str::FromStr};
+    use std::collections::HashMap;
 
     use itertools::Itertools;
 
+    use crate::error::error::AocError;
+    use crate::solution::solution::{Answer, Solution};
+
+    const YEAR: u16 = 2022;
+    const DAY: usize = 10;
+
     #[derive(Debug)]
     enum Instruction {
         Noop,
@@ -19,23 +24,19 @@ pub mod day10 {
         }
     }
 
-    impl FromStr for Instruction {
-        type Err = Box<dyn std::error::Error>;
-
-        fn from_str(s: &str) -> Result<Self, Self::Err> {
-            let mut parts = s.splitn(2, " ");
-
-            let token = parts.next().ok_or("invalid token")?;
-
-            let result = match token {
-                "noop" => Instruction::Noop,
-                "addx" => Instruction::Addx(parts.next().ok_or("invalid token parts")?.parse()?),
+    fn parse_instruction(text: &str, line: &str) -> Result<Instruction, AocError> {
+        let result = match line.split_once(' ') {
+            None if line == "noop" => Instruction::Noop,
+            Some(("addx", n)) => {
+                Instruction::Addx(n.parse().map_err(|e: std::num::ParseIntError| {
+                    AocError::parse(DAY, text, n, e.to_string())
+                })?)
+            }
 
-                _ => Err(format!("invalid token: {}", token))?,
-            };
+            _ => return Err(AocError::parse(DAY, text, line, "expected noop or addx N")),
+        };
 
-            Ok(result)
-        }
+        Ok(result)
     }
 
     fn run_instructions(instructions: Vec<Instruction>) -> HashMap<i32, i32> {
@@ -59,8 +60,10 @@ pub mod day10 {
         cycles
     }
 
-    fn read_instructions(text: String) -> Result<Vec<Instruction>, Box<(dyn std::error::Error)>> {
-        text.lines().map(|line| line.parse()).collect()
+    fn read_instructions(text: &str) -> Result<Vec<Instruction>, AocError> {
+        text.lines()
+            .map(|line| parse_instruction(text, line))
+            .collect()
     }
 
     fn get_cycle_value_at(cycles: &HashMap<i32, i32>, i: i32) -> i32 {
@@ -68,8 +71,8 @@ pub mod day10 {
         *cycles.get(key).unwrap()
     }
 
-    pub fn part1(text: String) -> Result<i32, Box<dyn std::error::Error>> {
-        let instructions = read_instructions(text)?;
+    pub fn part1(text: String) -> Result<i32, AocError> {
+        let instructions = read_instructions(&text)?;
 
         let cycles = run_instructions(instructions);
 
@@ -86,7 +89,7 @@ pub mod day10 {
         (i - 1) % 40
     }
 
-    pub fn part2(text: String) -> Result<String, Box<dyn std::error::Error>> {
+    pub fn part2(text: String) -> Result<String, AocError> {
         let scan_lines: Vec<Vec<i32>> = (0..6)
             .map(|i| {
                 let start = i * 40;
@@ -94,7 +97,7 @@ pub mod day10 {
             })
             .collect();
 
-        let instructions = read_instructions(text)?;
+        let instructions = read_instructions(&text)?;
         let cycles = run_instructions(instructions);
 
         let result: Vec<String> = scan_lines
@@ -117,6 +120,34 @@ pub mod day10 {
 
         Ok(result.join("\n"))
     }
+
+    pub struct Day10;
+
+    impl Solution for Day10 {
+        fn year(&self) -> u16 {
+            YEAR
+        }
+
+        fn day(&self) -> usize {
+            DAY
+        }
+
+        fn title(&self) -> &'static str {
+            "Cathode-Ray Tube"
+        }
+
+        fn part1(&self, text: String) -> Result<Answer, AocError> {
+            part1(text).map(Answer::from)
+        }
+
+        fn part2(&self, text: String) -> Result<Answer, AocError> {
+            part2(text).map(Answer::from)
+        }
+
+        fn parse(&self, text: &str) -> Option<Result<(), AocError>> {
+            Some(read_instructions(text).map(|_| ()))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -125,15 +156,16 @@ mod test {
 
     use super::day10;
 
+    const YEAR: u16 = 2022;
     const DAY: usize = 10;
 
     #[test]
     fn part1() {
-        run_day(DAY, day10::part1);
+        run_day(YEAR, DAY, 1, day10::part1);
     }
 
     #[test]
     fn part2() {
-        run_day(DAY, day10::part2);
+        run_day(YEAR, DAY, 2, day10::part2);
     }
 }
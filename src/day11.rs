@@ -1,9 +1,16 @@
-#[cfg(test)]
 pub mod day11 {
     use std::collections::HashMap;
+    use std::num::ParseIntError;
+    use std::str::{FromStr, Lines};
 
     use itertools::Itertools;
 
+    use crate::error::error::AocError;
+    use crate::solution::solution::{Answer, Solution};
+
+    const YEAR: u16 = 2022;
+    const DAY: usize = 11;
+
     #[derive(PartialEq, Debug, Clone, Copy)]
     enum Operation {
         Add(isize),
@@ -29,6 +36,7 @@ pub mod day11 {
 
     #[derive(Default, Debug)]
     struct Monkey {
+        #[allow(dead_code)]
         id: usize,
         items: Vec<isize>,
         divisible_by: isize,
@@ -43,77 +51,111 @@ pub mod day11 {
         }
     }
 
-    fn parse_monkey(monkey: &str) -> Result<Monkey, Box<dyn std::error::Error>> {
-        let mut lines = monkey.lines();
+    fn number<T: FromStr<Err = ParseIntError>>(text: &str, s: &str) -> Result<T, AocError> {
+        s.parse()
+            .map_err(|e: ParseIntError| AocError::parse(DAY, text, s, e.to_string()))
+    }
 
-        let id = lines
-            .next()
-            .unwrap()
-            .strip_prefix("Monkey ")
-            .unwrap()
-            .strip_suffix(":")
-            .unwrap()
-            .parse()?;
-
-        let items: Result<Vec<isize>, _> = lines
-            .next()
-            .ok_or("missing line item")?
-            .trim()
-            .trim_start_matches("Starting items: ")
-            .split(",")
-            .map(|s| s.trim().parse())
-            .collect();
+    /// Whatever follows `prefix` on the next line of a monkey.
+    fn field<'a>(
+        text: &str,
+        monkey: &'a str,
+        lines: &mut Lines<'a>,
+        prefix: &str,
+    ) -> Result<&'a str, AocError> {
+        let expected = || format!("expected {}", prefix.trim());
 
-        let operation_parts: Vec<&str> = lines
+        let line = lines
             .next()
-            .ok_or("missing operation")?
-            .trim()
-            .trim_start_matches("Operation: new =")
-            .split_whitespace()
+            .ok_or_else(|| AocError::parse(DAY, text, &monkey[monkey.len()..], expected()))?;
+
+        line.trim_start()
+            .strip_prefix(prefix)
+            .ok_or_else(|| AocError::parse(DAY, text, line, expected()))
+    }
+
+    fn parse_monkey(text: &str, monkey: &str) -> Result<Monkey, AocError> {
+        let mut lines = monkey.lines();
+
+        let id = field(text, monkey, &mut lines, "Monkey ")?;
+        let id = number(text, id.strip_suffix(':').unwrap_or(id))?;
+
+        let items: Result<Vec<isize>, _> = field(text, monkey, &mut lines, "Starting items: ")?
+            .split(", ")
+            .map(|s| number(text, s))
             .collect();
 
+        let operation = field(text, monkey, &mut lines, "Operation: new = ")?;
+        let operation_parts: Vec<&str> = operation.split_whitespace().collect();
+
         let op = match &operation_parts[..] {
             ["old", "*", "old"] => Operation::Squared,
-            ["old", "*", s] => Operation::Multiply(s.parse()?),
-            ["old", "+", s] => Operation::Add(s.parse()?),
-
-            _ => Err("invalid operation")?,
+            ["old", "*", s] => Operation::Multiply(number(text, s)?),
+            ["old", "+", s] => Operation::Add(number(text, s)?),
+
+            _ => {
+                return Err(AocError::parse(
+                    DAY,
+                    text,
+                    operation,
+                    "expected old * N, old + N or old * old",
+                ))
+            }
         };
 
-        let divisible_by = lines
-            .next()
-            .ok_or("missing divisible_by")?
-            .trim()
-            .trim_start_matches("Test: divisible by ")
-            .parse()?;
+        let divisible_by = field(text, monkey, &mut lines, "Test: divisible by ")?;
+        let divisible_by = match number(text, divisible_by)? {
+            0 => {
+                return Err(AocError::parse(
+                    DAY,
+                    text,
+                    divisible_by,
+                    "can't divide by 0",
+                ))
+            }
+            n => n,
+        };
 
-        let if_true = lines
-            .next()
-            .ok_or("missing if_true")?
-            .trim()
-            .trim_start_matches("If true: throw to monkey ")
-            .parse()?;
+        let if_true = number(
+            text,
+            field(text, monkey, &mut lines, "If true: throw to monkey ")?,
+        )?;
 
-        let if_false = lines
-            .next()
-            .ok_or("missing if_false")?
-            .trim()
-            .trim_start_matches("If false: throw to monkey ")
-            .parse()?;
+        let if_false = number(
+            text,
+            field(text, monkey, &mut lines, "If false: throw to monkey ")?,
+        )?;
 
         Ok(Monkey {
-            id: id,
+            id,
             items: items?,
-            op: op,
-            divisible_by: divisible_by,
-            if_true: if_true,
-            if_false: if_false,
-            ..Default::default()
+            op,
+            divisible_by,
+            if_true,
+            if_false,
         })
     }
 
+    fn parse_monkeys(text: &str) -> Result<Vec<Monkey>, AocError> {
+        let monkeys: Vec<Monkey> = text
+            .split("\n\n")
+            .map(|monkey| parse_monkey(text, monkey))
+            .collect::<Result<_, _>>()?;
+
+        for (i, m) in monkeys.iter().enumerate() {
+            if m.if_true >= monkeys.len() || m.if_false >= monkeys.len() {
+                return Err(AocError::invalid_input(
+                    DAY,
+                    format!("monkey {} throws to a monkey that doesn't exist", i),
+                ));
+            }
+        }
+
+        Ok(monkeys)
+    }
+
     fn execute_round_vec<F>(
-        monkeys: &mut Vec<Monkey>,
+        monkeys: &mut [Monkey],
         inspections: &mut HashMap<isize, isize>,
         worry_manager: &F,
     ) where
@@ -130,9 +172,7 @@ pub mod day11 {
                 .collect();
 
             for item in items {
-                inspections
-                    .entry(i as isize)
-                    .and_modify(|e| *e = e.clone() + 1);
+                inspections.entry(i as isize).and_modify(|e| *e += 1);
                 let result = monkeys[i].test(item);
                 let loc = if result {
                     monkeys[i].if_true
@@ -144,11 +184,8 @@ pub mod day11 {
         }
     }
 
-    pub fn part1(text: String) -> Result<isize, Box<dyn std::error::Error>> {
-        let data = text.split("\n\n");
-
-        let monkeys: Result<Vec<Monkey>, _> = data.map(parse_monkey).collect();
-        let mut monkeys = monkeys?;
+    pub fn part1(text: String) -> Result<isize, AocError> {
+        let mut monkeys = parse_monkeys(&text)?;
 
         let mut inspections: HashMap<isize, isize> =
             HashMap::from_iter((0..monkeys.len()).map(|i| (i as isize, 0)));
@@ -159,22 +196,13 @@ pub mod day11 {
             execute_round_vec(&mut monkeys, &mut inspections, &|i| i / 3);
         }
 
-        let result: isize = inspections
-            .iter()
-            .map(|(_, v)| v)
-            .sorted()
-            .rev()
-            .take(2)
-            .product();
+        let result: isize = inspections.values().sorted().rev().take(2).product();
 
         Ok(result)
     }
 
-    pub fn part2(text: String) -> Result<isize, Box<dyn std::error::Error>> {
-        let data = text.split("\n\n");
-
-        let monkeys: Result<Vec<Monkey>, _> = data.map(parse_monkey).collect();
-        let mut monkeys = monkeys?;
+    pub fn part2(text: String) -> Result<isize, AocError> {
+        let mut monkeys = parse_monkeys(&text)?;
 
         let mut inspections: HashMap<isize, isize> =
             HashMap::from_iter((0..monkeys.len()).map(|i| (i as isize, 0)));
@@ -187,23 +215,46 @@ pub mod day11 {
             execute_round_vec(&mut monkeys, &mut inspections, &|i| i % common_divisor);
         }
 
-        let result: isize = inspections
-            .iter()
-            .map(|(_, v)| v)
-            .sorted()
-            .rev()
-            .take(2)
-            .product();
+        let result: isize = inspections.values().sorted().rev().take(2).product();
 
         Ok(result)
     }
 
+    pub struct Day11;
+
+    impl Solution for Day11 {
+        fn year(&self) -> u16 {
+            YEAR
+        }
+
+        fn day(&self) -> usize {
+            DAY
+        }
+
+        fn title(&self) -> &'static str {
+            "Monkey in the Middle"
+        }
+
+        fn part1(&self, text: String) -> Result<Answer, AocError> {
+            part1(text).map(Answer::from)
+        }
+
+        fn part2(&self, text: String) -> Result<Answer, AocError> {
+            part2(text).map(Answer::from)
+        }
+
+        fn parse(&self, text: &str) -> Option<Result<(), AocError>> {
+            Some(parse_monkeys(text).map(|_| ()))
+        }
+    }
+
     #[cfg(test)]
     mod test {
         use crate::helper::helper::run_day;
 
         use super::*;
 
+        const YEAR: u16 = 2022;
         const DAY: usize = 11;
 
         const MONKEY: &str = r#"Monkey 0:
@@ -215,7 +266,7 @@ pub mod day11 {
 
         #[test]
         fn test_parse_monkey() {
-            let monkey = parse_monkey(MONKEY).unwrap();
+            let monkey = parse_monkey(MONKEY, MONKEY).unwrap();
 
             assert_eq!(monkey.id, 0);
             assert_eq!(monkey.items, vec![79, 98]);
@@ -227,12 +278,12 @@ pub mod day11 {
 
         #[test]
         fn test_part1() {
-            run_day(DAY, part1);
+            run_day(YEAR, DAY, 1, part1);
         }
 
         #[test]
         fn test_part2() {
-            run_day(DAY, part2);
+            run_day(YEAR, DAY, 2, part2);
         }
     }
 }
@@ -1,282 +1,164 @@
-#![allow(unused)] // FIXME
-
-#[cfg(test)]
 pub mod day12 {
-    use std::{
-        cmp::Reverse,
-        collections::{BinaryHeap, HashMap, HashSet},
-        fmt::Debug,
-    };
-
-    use itertools::Itertools;
-    use priority_queue::PriorityQueue;
-
-    struct Grid<T>
-    where
-        T: std::ops::Sub<Output = T> + PartialOrd + From<isize> + Copy + Debug,
-    {
-        cells: Vec<Vec<T>>,
+    use crate::cancel::cancel::check;
+    use crate::error::error::AocError;
+    use crate::grid::grid::{Grid, Pos};
+    use crate::progress::progress::{phase, step};
+    use crate::search::search::{astar, bfs, Graph, Reversed, ReversibleGraph};
+    use crate::solution::solution::{Answer, Solution};
+    use crate::trace;
+
+    const YEAR: u16 = 2022;
+    const DAY: usize = 12;
+
+    /// The heightmap, where every step goes at most one up.
+    struct Hill {
+        heights: Grid<isize>,
     }
 
-    impl<T> Grid<T>
-    where
-        T: std::ops::Sub<Output = T> + PartialOrd + From<isize> + Copy + Debug,
-    {
-        fn get(&self, x: isize, y: isize) -> Option<&T> {
-            let val = self.cells.get(y as usize)?.get(x as usize);
-
-            // println!("{x},{y} = {val:?}");
-
-            val
+    impl Hill {
+        /// Counts a cell as visited, and whether to go on from it: once the
+        /// part is cancelled nothing leads anywhere, so the search runs dry
+        /// and the caller's `check` says why.
+        fn visit(&self) -> bool {
+            step();
+            check(DAY).is_ok()
         }
 
-        fn neighbors(&self, x: isize, y: isize) -> Vec<((isize, isize), &T)> {
-            [(x - 1, y), (x, y - 1), (x + 1, y), (x, y + 1)]
-                .iter()
-                .flat_map(|&v| match self.get(v.0, v.1) {
-                    Some(val) => Some((v, val)),
-                    None => None,
-                })
-                .collect_vec()
+        fn cells(&self) -> u64 {
+            (self.heights.width() * self.heights.height()) as u64
         }
+    }
 
-        fn connected_neighbors(&self, x: isize, y: isize) -> Vec<(isize, isize)> {
-            let my_height = match self.get(x, y) {
-                Some(h) => h,
-                None => panic!("no get for value {x},{y}"),
-            };
-
-            self.neighbors(x, y)
-                .iter()
-                .filter(|(_, &height)| {
-                    let val = height - *my_height;
-                    val <= 1.into()
-                })
-                .map(|(loc, _)| *loc)
-                .collect()
-        }
+    impl Graph for Hill {
+        type Node = Pos;
 
-        fn distance(&self, current: (isize, isize), neighbor: (isize, isize)) -> T {
-            *self.get(neighbor.0, neighbor.1).unwrap() - *self.get(current.0, current.1).unwrap()
-        }
-    }
+        fn neighbors(&self, pos: Pos) -> Vec<Pos> {
+            if !self.visit() {
+                return vec![];
+            }
+            let my_height = self.heights[pos];
 
-    fn parse_board(text: &str) -> Grid<isize> {
-        Grid {
-            cells: text
-                .lines()
-                .map(|line| {
-                    line.chars()
-                        .map(|c| {
-                            if c == 'S' {
-                                'a'
-                            } else if c == 'E' {
-                                'z'
-                            } else {
-                                c
-                            }
-                        })
-                        .map(|c| (c as u8 - 'a' as u8).into())
-                        .collect()
-                })
-                .collect(),
+            self.heights
+                .neighbors4(pos)
+                .filter(|&loc| self.heights[loc] - my_height <= 1)
+                .collect()
         }
     }
 
-    pub fn part1(text: String) -> Result<usize, Box<dyn std::error::Error>> {
-        let g = parse_board(&text[..]);
+    impl ReversibleGraph for Hill {
+        fn predecessors(&self, pos: Pos) -> Vec<Pos> {
+            if !self.visit() {
+                return vec![];
+            }
+            let my_height = self.heights[pos];
 
-        let start = text.lines().join("").find("S").unwrap();
-        let start = (
-            (start % g.cells[0].len()) as isize,
-            (start / g.cells[0].len()) as isize,
-        );
-        let end = text.lines().join("").find("E").unwrap();
-        let end = (
-            (end % g.cells[0].len()) as isize,
-            (end / g.cells[0].len()) as isize,
-        );
+            self.heights
+                .neighbors4(pos)
+                .filter(|&loc| my_height - self.heights[loc] <= 1)
+                .collect()
+        }
+    }
 
-        let prev = djikstra(&g, start, end)?;
+    fn parse_board(text: &str) -> Result<Grid<char>, AocError> {
+        Grid::parse(DAY, text, "expected a height from a to z, S or E", |c| {
+            matches!(c, 'a'..='z' | 'S' | 'E').then_some(c)
+        })
+    }
 
-        let mut path: Vec<(isize, isize)> = unroll(prev, end);
-        path.retain(|&e| e != start);
+    fn heights(board: &Grid<char>) -> Grid<isize> {
+        board.map(|&c| match c {
+            'S' => 0,
+            'E' => 25,
+            _ => (c as u8 - b'a').into(),
+        })
+    }
 
-        Ok(path.len())
+    /// Where the `S` or `E` marker is.
+    fn find_marker(board: &Grid<char>, marker: char) -> Result<Pos, AocError> {
+        board
+            .position(|&c| c == marker)
+            .ok_or_else(|| AocError::invalid_input(DAY, format!("there is no {} marker", marker)))
     }
 
-    fn show_output(text: &str, path: &Vec<(isize, isize)>) -> String {
-        let mut view: Vec<Vec<char>> = text.lines().map(|line| line.chars().collect()).collect();
+    pub fn part1(text: String) -> Result<usize, AocError> {
+        let board = parse_board(&text[..])?;
+        let hill = Hill {
+            heights: heights(&board),
+        };
 
-        for &(x, y) in path.iter() {
-            view[y as usize][x as usize] = '.';
-        }
+        let start = find_marker(&board, 'S')?;
+        let end = find_marker(&board, 'E')?;
+
+        // every step costs 1 and moves 1, so the manhattan distance never
+        // guesses too much
+        phase("climbing from S", hill.cells());
+        let search = astar(&hill, [start], end, |pos| pos.manhattan(end));
+        check(DAY)?;
+        let path = search
+            .path(end)
+            .ok_or_else(|| AocError::no_solution(DAY, "the end can't be reached from S"))?;
 
-        let output = view.iter().map(|line| line.iter().join("")).join("\n");
+        trace!("the path:\n{}", show_output(&board, &path));
 
-        output
+        Ok(path.len() - 1)
     }
 
-    fn unroll(
-        prev: HashMap<(isize, isize), (isize, isize)>,
-        end: (isize, isize),
-    ) -> Vec<(isize, isize)> {
-        let mut result = vec![];
+    fn show_output(board: &Grid<char>, path: &[Pos]) -> String {
+        let mut view = board.clone();
 
-        let mut current = Some(&end);
-        while let Some(u) = current {
-            result.insert(0, u);
-            current = prev.get(u);
+        for &p in path.iter() {
+            view[p] = '.';
         }
 
-        result.iter().map(|&u| u.clone()).collect()
+        view.to_string()
     }
 
-    fn a_star(
-        grid: Grid<isize>,
-        start: (isize, isize),
-        goal: (isize, isize),
-    ) -> Option<Vec<(isize, isize)>> {
-        let mut open_set = BinaryHeap::from([(0, start)]);
-
-        let mut came_from: HashMap<(isize, isize), (isize, isize)> = HashMap::new();
+    pub fn part2(text: String) -> Result<usize, AocError> {
+        let board = parse_board(&text[..])?;
+        let hill = Hill {
+            heights: heights(&board),
+        };
 
-        let mut g_score = HashMap::from([(start, 0)]);
-        // let mut f_score = HashMap::from([(start, 0)]);
+        let end = find_marker(&board, 'E')?;
 
-        while !open_set.is_empty() {
-            let (_, current) = open_set.pop().unwrap();
-            if current == goal {
-                // this is the money
-                return Some(construct_path(came_from, current));
-            }
-
-            for neighbor in grid.connected_neighbors(current.0, current.1) {
-                // distance is always 1
-                let tentative_score = g_score.get(&current).unwrap() + 1;
-                // g_score.get(&current).unwrap() + grid.distance(current, neighbor);
-
-                if tentative_score < *g_score.get(&neighbor).unwrap_or(&std::isize::MAX) {
-                    came_from.insert(neighbor, current);
-                    g_score.insert(neighbor, tentative_score);
-                    let f_score = tentative_score + h(neighbor, goal);
-                    if None == open_set.iter().find(|(_, v)| *v == neighbor) {
-                        open_set.push((f_score, neighbor))
-                    }
-                }
-            }
-        }
+        // walk down from the end, and the first a reached is the closest
+        phase("walking down from E", hill.cells());
+        let search = bfs(&Reversed(&hill), [end], |pos| hill.heights[pos] == 0);
+        check(DAY)?;
+        let shortest = search
+            .found()
+            .and_then(|a| search.distance(a))
+            .ok_or_else(|| AocError::no_solution(DAY, "the end can't be reached from any a"))?;
 
-        None
+        Ok(shortest)
     }
 
-    fn djikstra(
-        grid: &Grid<isize>,
-        start: (isize, isize),
-        goal: (isize, isize),
-    ) -> Result<HashMap<(isize, isize), (isize, isize)>, &str> {
-        let mut prev = HashMap::<(isize, isize), (isize, isize)>::new();
-
-        let vertexes: Vec<(isize, isize)> = (0..grid.cells[0].len() as isize)
-            .cartesian_product(0..grid.cells.len() as isize)
-            .collect();
-
-        let mut q = PriorityQueue::new();
+    pub struct Day12;
 
-        for vertex in vertexes {
-            q.push(vertex, Reverse(std::isize::MAX));
+    impl Solution for Day12 {
+        fn year(&self) -> u16 {
+            YEAR
         }
-        q.change_priority(&start, Reverse(0));
 
-        while !q.is_empty() {
-            let (u, dist_u) = q.pop().unwrap();
-
-            if u == goal {
-                break;
-            }
-
-            if dist_u.0 == std::isize::MAX {
-                // this is unknown
-                // this only happens if nothing so far has found it's way to this node
-                // so, the node is not connected to the starting node, and we could never
-                // get here
-                // this means that we need to validate that the path actually worked.
-                break;
-            }
-
-            if dist_u.0 == std::isize::MAX {
-                println!("{u:?}");
-            }
-
-            for v in grid.connected_neighbors(u.0, u.1) {
-                if let Some((&v, &dist_v)) = q.get(&v) {
-                    // distance is always 1
-
-                    let alt = dist_u.0 + 1;
-                    if alt < dist_v.0 {
-                        q.change_priority(&v, Reverse(alt));
-                        prev.insert(v, u);
-                    }
-                }
-            }
+        fn day(&self) -> usize {
+            DAY
         }
 
-        if prev.contains_key(&goal) {
-            Ok(prev)
-        } else {
-            Err("not a connected graph")
+        fn title(&self) -> &'static str {
+            "Hill Climbing Algorithm"
         }
-    }
 
-    fn h(neighbor: (isize, isize), goal: (isize, isize)) -> isize {
-        ((goal.0 - neighbor.0).abs() + (goal.1 - neighbor.1).abs()) + 100
-    }
-
-    fn construct_path(
-        came_from: HashMap<(isize, isize), (isize, isize)>,
-        current: (isize, isize),
-    ) -> Vec<(isize, isize)> {
-        let mut current = current;
-        let mut path = vec![current];
-        while came_from.contains_key(&current) {
-            current = *came_from.get(&current).unwrap();
-            path.insert(0, current);
+        fn part1(&self, text: String) -> Result<Answer, AocError> {
+            part1(text).map(Answer::from)
         }
 
-        path
-    }
-
-    pub fn part2(text: String) -> Result<isize, Box<dyn std::error::Error>> {
-        let g = parse_board(&text[..]);
-
-        let end = text.lines().join("").find("E").unwrap();
-        let end = (
-            (end % g.cells[0].len()) as isize,
-            (end / g.cells[0].len()) as isize,
-        );
-
-        let mut all_a = HashSet::new();
-
-        for (y, row) in g.cells.iter().enumerate() {
-            for (x, &cell) in row.iter().enumerate() {
-                if cell == 0 {
-                    all_a.insert((x as isize, y as isize));
-                }
-            }
+        fn part2(&self, text: String) -> Result<Answer, AocError> {
+            part2(text).map(Answer::from)
         }
 
-        let mut distances = HashSet::new();
-        for start in all_a {
-            let prev = djikstra(&g, start, end);
-
-            if let Ok(prev) = prev {
-                let mut path = unroll(prev, end);
-                path.retain(|&v| v != start);
-                distances.insert(path.len());
-            }
+        fn parse(&self, text: &str) -> Option<Result<(), AocError>> {
+            Some(parse_board(text).map(|_| ()))
         }
-
-        return Ok(*distances.iter().min().unwrap() as isize);
     }
 
     #[cfg(test)]
@@ -285,32 +167,17 @@ pub mod day12 {
 
         use super::*;
 
+        const YEAR: u16 = 2022;
         const DAY: usize = 12;
 
-        #[test]
-        fn test_binheap() {
-            let start = (0, (0, 0));
-            let mut heap = BinaryHeap::from([start]);
-
-            heap.push((11, (1, 1)));
-            heap.push((22, (2, 2)));
-            heap.push((1, (5, 5)));
-            heap.push((2, (5, 5)));
-            heap.push((11111, (5, 5)));
-
-            while !heap.is_empty() {
-                dbg!(heap.pop());
-            }
-        }
-
         #[test]
         fn test_part1() {
-            run_day(DAY, part1);
+            run_day(YEAR, DAY, 1, part1);
         }
 
         #[test]
         fn test_part2() {
-            run_day(DAY, part2);
+            run_day(YEAR, DAY, 2, part2);
         }
     }
 }
@@ -1,24 +1,49 @@
-#[cfg(test)]
 pub mod day13 {
-    use core::panic;
     use std::cmp::Ordering;
 
     use serde_json::Value;
 
-    fn compare_str(a: &str, b: &str) -> Result<Ordering, Box<dyn std::error::Error>> {
-        let a: serde_json::Result<Value> = serde_json::from_str(a);
-        let a = a?;
-        let b: serde_json::Result<Value> = serde_json::from_str(b);
-        let b = b?;
+    use crate::error::error::AocError;
+    use crate::solution::solution::{Answer, Solution};
 
-        if let (Value::Array(a), Value::Array(b)) = (a, b) {
-            return Ok(compare_vec(&a, &b));
+    const YEAR: u16 = 2022;
+    const DAY: usize = 13;
+
+    /// Only lists and integers can be compared.
+    fn valid(v: &Value) -> bool {
+        match v {
+            Value::Number(n) => n.is_i64(),
+            Value::Array(a) => a.iter().all(valid),
+            _ => false,
         }
+    }
 
-        Err("invalid input".into())
+    fn parse_packet(text: &str, line: &str) -> Result<Vec<Value>, AocError> {
+        let value: Value = serde_json::from_str(line).map_err(|e| {
+            // serde_json adds its own " at line 1 column N"
+            let message = e.to_string();
+            let message = message.split(" at line ").next().unwrap_or_default();
+            let at = line.get(e.column().saturating_sub(1)..).unwrap_or(line);
+            AocError::parse(DAY, text, at, message)
+        })?;
+
+        match value {
+            Value::Array(a) if a.iter().all(valid) => Ok(a),
+            _ => Err(AocError::parse(
+                DAY,
+                text,
+                line,
+                "expected a list of lists and integers",
+            )),
+        }
     }
 
-    fn compare_vec(a: &Vec<Value>, b: &Vec<Value>) -> Ordering {
+    #[cfg(test)]
+    fn compare_str(a: &str, b: &str) -> Result<Ordering, AocError> {
+        Ok(compare_vec(&parse_packet(a, a)?, &parse_packet(b, b)?))
+    }
+
+    fn compare_vec(a: &[Value], b: &[Value]) -> Ordering {
         for (left, right) in a.iter().zip(b.iter()) {
             let cmp = match (left, right) {
                 (Value::Null, Value::Null) => Ordering::Equal,
@@ -31,15 +56,11 @@ pub mod day13 {
 
                 (Value::Array(l), Value::Array(r)) => compare_vec(l, r),
 
-                (Value::Array(l), Value::Number(r)) => {
-                    compare_vec(l, &vec![Value::Number(r.clone())])
-                }
+                (Value::Array(l), Value::Number(r)) => compare_vec(l, &[Value::Number(r.clone())]),
 
-                (Value::Number(l), Value::Array(r)) => {
-                    compare_vec(&vec![Value::Number(l.clone())], r)
-                }
+                (Value::Number(l), Value::Array(r)) => compare_vec(&[Value::Number(l.clone())], r),
 
-                v => panic!("not handled. they lied about the ruleset {v:?}"),
+                v => unreachable!("packets are checked when parsed, not {v:?}"),
             };
 
             if cmp != Ordering::Equal {
@@ -53,13 +74,23 @@ pub mod day13 {
         a.len().cmp(&b.len())
     }
 
-    pub fn part1(text: String) -> Result<usize, Box<dyn std::error::Error>> {
+    pub fn part1(text: String) -> Result<usize, AocError> {
         let mut results = vec![];
-        for chunks in text.split("\n\n") {
-            let pieces: Vec<&str> = chunks.lines().collect();
-
-            let result = compare_str(pieces[0], pieces[1])?;
-            results.push(result);
+        for chunk in text.split("\n\n") {
+            let mut packets = chunk.lines().map(|line| parse_packet(&text, line));
+
+            let (left, right) = match (packets.next(), packets.next()) {
+                (Some(left), Some(right)) => (left?, right?),
+                _ => {
+                    return Err(AocError::parse(
+                        DAY,
+                        &text,
+                        chunk,
+                        "expected a pair of packets",
+                    ))
+                }
+            };
+            results.push(compare_vec(&left, &right));
         }
 
         let correctly_ordered = results
@@ -72,24 +103,17 @@ pub mod day13 {
         Ok(sum)
     }
 
-    fn parse_serde_json_array(s: &str) -> Vec<Value> {
-        match serde_json::from_str(s).unwrap() {
-            Value::Array(a) => a,
-            _ => panic!("this is not possible"),
-        }
-    }
-
-    pub fn part2(text: String) -> Result<usize, Box<dyn std::error::Error>> {
+    pub fn part2(text: String) -> Result<usize, AocError> {
         let mut list: Vec<Vec<Value>> = text
             .lines()
-            .filter(|&s| s != "")
-            .map(|s| parse_serde_json_array(s))
-            .collect();
+            .filter(|&s| !s.is_empty())
+            .map(|line| parse_packet(&text, line))
+            .collect::<Result<_, _>>()?;
 
         let marker1_str = "[[2]]";
-        let marker1 = parse_serde_json_array(marker1_str);
+        let marker1 = parse_packet(marker1_str, marker1_str)?;
         let marker2_str = "[[6]]";
-        let marker2 = parse_serde_json_array(marker2_str);
+        let marker2 = parse_packet(marker2_str, marker2_str)?;
 
         list.push(marker1);
         list.push(marker2);
@@ -108,12 +132,38 @@ pub mod day13 {
         Ok(index_marker1 * index_marker2)
     }
 
+    pub struct Day13;
+
+    impl Solution for Day13 {
+        fn year(&self) -> u16 {
+            YEAR
+        }
+
+        fn day(&self) -> usize {
+            DAY
+        }
+
+        fn title(&self) -> &'static str {
+            "Distress Signal"
+        }
+
+        fn part1(&self, text: String) -> Result<Answer, AocError> {
+            part1(text).map(Answer::from)
+        }
+
+        fn part2(&self, text: String) -> Result<Answer, AocError> {
+            part2(text).map(Answer::from)
+        }
+    }
+
     #[cfg(test)]
     mod test {
+        use crate::debug;
         use crate::helper::helper::run_day;
 
         use super::*;
 
+        const YEAR: u16 = 2022;
         const DAY: usize = 13;
 
         #[test]
@@ -121,10 +171,10 @@ pub mod day13 {
             let val: serde_json::Result<Value> = serde_json::from_str("[]");
             let val = val.unwrap();
 
-            dbg!(&val);
+            debug!("{:?}", val);
 
-            dbg!(&val[0]);
-            dbg!(&val[1]);
+            debug!("{:?}", val[0]);
+            debug!("{:?}", val[1]);
         }
 
         #[test]
@@ -157,12 +207,12 @@ pub mod day13 {
 
         #[test]
         fn test_part1() {
-            run_day(DAY, part1);
+            run_day(YEAR, DAY, 1, part1);
         }
 
         #[test]
         fn test_part2() {
-            run_day(DAY, part2);
+            run_day(YEAR, DAY, 2, part2);
         }
     }
 }
@@ -1,15 +1,24 @@
-#[cfg(test)]
 pub mod day14 {
     use std::fmt::{Display, Formatter};
 
     use nom::bytes::complete::tag;
     use nom::character::complete;
-    use nom::character::complete::newline;
+    use nom::combinator::all_consuming;
     use nom::multi::separated_list1;
     use nom::sequence::separated_pair;
     use nom::IResult;
 
-    const SAND_SPAWN: (usize, usize) = (500, 0);
+    use crate::cancel::cancel::check;
+    use crate::error::error::AocError;
+    use crate::point::point::{Direction8, Point};
+    use crate::solution::solution::{Answer, Solution};
+    use crate::sparse::sparse::{Pos, SparseGrid};
+    use crate::trace;
+
+    const YEAR: u16 = 2022;
+    const DAY: usize = 14;
+
+    const SAND_SPAWN: Pos = Point::new(500, 0);
 
     #[derive(Copy, Clone, PartialEq, Debug)]
     enum Tile {
@@ -27,8 +36,8 @@ pub mod day14 {
     }
 
     impl Tile {
-        fn to_char(&self) -> char {
-            match *self {
+        fn to_char(self) -> char {
+            match self {
                 Tile::Air => '.',
                 Tile::Rock => '#',
                 Tile::Sand => 'O',
@@ -37,126 +46,104 @@ pub mod day14 {
     }
 
     struct World {
-        cells: Vec<Vec<Tile>>,
-        min: (usize, usize),
-        max: (usize, usize),
+        cells: SparseGrid<Tile>,
+        /// the lowest rock, sand past it falls forever
+        bottom: isize,
+        /// part 2's floor, as wide as the sand needs it to be
+        floor: Option<isize>,
     }
 
     impl World {
-        fn new(size: usize) -> World {
-            World {
-                cells: (0..size)
-                    .map(|_| (0..size).map(|_| Tile::Air).collect())
-                    .collect(),
-                min: (usize::MAX, usize::MAX),
-                max: (0, 0),
+        fn get(&self, loc: Pos) -> Tile {
+            match self.floor {
+                Some(floor) if loc.y == floor => Tile::Rock,
+                _ => *self.cells.get(loc),
             }
         }
 
-        fn get(&self, loc: (usize, usize)) -> Option<&Tile> {
-            self.cells.get(loc.1)?.get(loc.0)
-        }
-
         fn count_sands(&self) -> usize {
-            self.cells
-                .iter()
-                .map(|row| row.iter().filter(|&&t| t == Tile::Sand).count())
-                .sum()
+            self.cells.iter().filter(|&(_, &t)| t == Tile::Sand).count()
         }
 
-        fn update_loc_seen(&mut self, loc: (usize, usize)) {
-            self.min = (self.min.0.min(loc.0), self.min.1.min(loc.1));
-            self.max = (self.max.0.max(loc.0), self.max.1.max(loc.1));
+        fn set(&mut self, loc: Pos, tile: Tile) {
+            self.cells.set(loc, tile);
         }
 
-        fn set(&mut self, loc: (usize, usize), tile: Tile) {
-            self.update_loc_seen(loc);
-            self.cells[loc.1][loc.0] = tile;
-        }
-
-        fn draw_world(&self) {
-            let lines: Vec<String> = (self.min.1..=self.max.1)
-                .map(|y| {
-                    (self.min.0..=self.max.0)
-                        .map(|x| self.get((x, y)).unwrap().to_char())
-                        .collect()
-                })
-                .collect();
-
-            for line in lines {
-                println!("{line}");
-            }
+        fn draw_world(&self) -> String {
+            self.cells.render_all(|t| t.to_char())
         }
     }
 
-    fn point(input: &str) -> IResult<&str, (usize, usize)> {
+    fn point(input: &str) -> IResult<&str, Pos> {
         let (input, (start, end)) = separated_pair(complete::u32, tag(","), complete::u32)(input)?;
 
-        Ok((input, (start as usize, end as usize)))
+        Ok((input, Point::new(start as isize, end as isize)))
     }
 
-    fn line_nodes(input: &str) -> IResult<&str, Vec<(usize, usize)>> {
+    fn line_nodes(input: &str) -> IResult<&str, Vec<Pos>> {
         separated_list1(tag(" -> "), point)(input)
     }
 
-    pub fn parse_input(input: &str) -> Vec<Vec<(usize, usize)>> {
-        let (_, result) = separated_list1(newline, line_nodes)(input).unwrap();
-        result
+    pub fn parse_input(input: &str) -> Result<Vec<Vec<Pos>>, AocError> {
+        input
+            .lines()
+            .map(|line| {
+                all_consuming(line_nodes)(line)
+                    .map(|(_, nodes)| nodes)
+                    .map_err(|e| AocError::nom(DAY, input, e))
+            })
+            .collect()
     }
 
-    fn bottom_neighbors_ordered(loc: (usize, usize)) -> [(usize, usize); 3] {
-        [
-            // the middle is the leftmost because we fall straight down first
-            (loc.0, loc.1 + 1),
-            // then we fall to the left of that
-            (loc.0 - 1, loc.1 + 1),
-            // then to right
-            (loc.0 + 1, loc.1 + 1),
-        ]
-    }
-
-    fn populate_world(text: &str, size: usize) -> World {
-        let mut world = World::new(size);
-        let list_of_line_nodes = parse_input(text);
+    // straight down first, then down to the left, then down to the right
+    const FALLS: [Direction8; 3] = [
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::SouthEast,
+    ];
+
+    fn populate_world(text: &str) -> Result<World, AocError> {
+        let mut world = World {
+            cells: SparseGrid::new(Tile::Air),
+            bottom: 0,
+            floor: None,
+        };
+        let list_of_line_nodes = parse_input(text)?;
 
         for line_node in list_of_line_nodes {
             for window in line_node.windows(2) {
-                let start = window[0];
-                let end = window[1];
-
-                for x in start.0.min(end.0)..=start.0.max(end.0) {
-                    world.set((x, start.1), Tile::Rock);
-                }
+                let mut current = window[0];
+                world.set(current, Tile::Rock);
 
-                for y in start.1.min(end.1)..=start.1.max(end.1) {
-                    world.set((start.0, y), Tile::Rock);
+                while current != window[1] {
+                    current = current.step_toward(window[1]);
+                    world.set(current, Tile::Rock);
                 }
             }
         }
 
-        world
+        world.bottom = world.cells.bounds().map_or(0, |(_, max)| max.y);
+        Ok(world)
     }
 
-    pub fn part1(text: String) -> Result<usize, Box<dyn std::error::Error>> {
-        let mut world = populate_world(&text[..], 1000);
+    pub fn part1(text: String) -> Result<usize, AocError> {
+        let mut world = populate_world(&text[..])?;
 
-        world.draw_world();
+        trace!("the cave:\n{}", world.draw_world());
 
         'outer: loop {
+            check(DAY)?;
             let mut current_loc = SAND_SPAWN;
             loop {
-                let next_loc = bottom_neighbors_ordered(current_loc)
+                let next_loc = FALLS
                     .iter()
-                    .flat_map(|&loc| match world.get(loc) {
-                        Some(Tile::Air) => Some(loc),
-                        _ => None,
-                    })
-                    .next();
+                    .map(|fall| current_loc + fall.step())
+                    .find(|&loc| world.get(loc) == Tile::Air);
 
                 match next_loc {
                     Some(loc) => {
                         current_loc = loc;
-                        if current_loc.1 > world.max.1 {
+                        if current_loc.y > world.bottom {
                             break 'outer;
                         }
                     }
@@ -170,36 +157,24 @@ pub mod day14 {
             world.set(current_loc, Tile::Sand);
         }
 
-        world.draw_world();
+        trace!("the cave:\n{}", world.draw_world());
         Ok(world.count_sands())
     }
 
-    pub fn part2(text: String) -> Result<usize, Box<dyn std::error::Error>> {
-        let size = 1000;
-        let mut world = populate_world(&text[..], size);
-
-        let old_min = world.min;
-        let old_max = world.max;
-        let floor_y = world.max.1 + 2;
-        for x in 0..size {
-            world.set((x, floor_y), Tile::Rock);
-        }
-
-        world.min = old_min;
-        world.max = old_max;
+    pub fn part2(text: String) -> Result<usize, AocError> {
+        let mut world = populate_world(&text[..])?;
+        world.floor = Some(world.bottom + 2);
 
-        world.draw_world();
+        trace!("the cave:\n{}", world.draw_world());
 
         'outer: loop {
+            check(DAY)?;
             let mut current_loc = SAND_SPAWN;
             loop {
-                let next_loc = bottom_neighbors_ordered(current_loc)
+                let next_loc = FALLS
                     .iter()
-                    .flat_map(|&loc| match world.get(loc) {
-                        Some(Tile::Air) => Some(loc),
-                        _ => None,
-                    })
-                    .next();
+                    .map(|fall| current_loc + fall.step())
+                    .find(|&loc| world.get(loc) == Tile::Air);
 
                 match next_loc {
                     Some(loc) => {
@@ -219,40 +194,65 @@ pub mod day14 {
             }
         }
 
-        println!("================================================================");
-        println!("================================================================");
-        println!("================================================================");
-        println!("================================================================");
-        world.draw_world();
+        trace!("the filled cave:\n{}", world.draw_world());
         Ok(world.count_sands())
     }
 
+    pub struct Day14;
+
+    impl Solution for Day14 {
+        fn year(&self) -> u16 {
+            YEAR
+        }
+
+        fn day(&self) -> usize {
+            DAY
+        }
+
+        fn title(&self) -> &'static str {
+            "Regolith Reservoir"
+        }
+
+        fn part1(&self, text: String) -> Result<Answer, AocError> {
+            part1(text).map(Answer::from)
+        }
+
+        fn part2(&self, text: String) -> Result<Answer, AocError> {
+            part2(text).map(Answer::from)
+        }
+
+        fn parse(&self, text: &str) -> Option<Result<(), AocError>> {
+            Some(parse_input(text).map(|_| ()))
+        }
+    }
+
     #[cfg(test)]
     mod test {
         use crate::helper::helper::{dbg_day, run_day};
 
         use super::*;
 
+        const YEAR: u16 = 2022;
         const DAY: usize = 14;
 
         #[test]
         fn test_part1() {
-            run_day(DAY, part1);
+            run_day(YEAR, DAY, 1, part1);
         }
 
         #[test]
         fn test_part2() {
-            run_day(DAY, part2);
+            run_day(YEAR, DAY, 2, part2);
         }
 
         #[test]
         fn dbg_part1() {
-            dbg_day(DAY, part1);
+            dbg_day(YEAR, DAY, part1);
         }
 
         #[test]
         fn dbg_part2() {
-            dbg_day(DAY, part2);
+            dbg_day(YEAR, DAY, part2);
         }
     }
 }
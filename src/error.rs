@@ -0,0 +1,259 @@
+pub mod error {
+    use std::error::Error;
+    use std::fmt;
+    use std::path::PathBuf;
+
+    use nom::error::ErrorKind;
+
+    #[derive(Debug)]
+    pub enum AocError {
+        /// The input doesn't have the shape the day expects. `line` and
+        /// `column` count from 1, `snippet` is the whole offending line.
+        Parse {
+            day: usize,
+            line: usize,
+            column: usize,
+            snippet: String,
+            message: String,
+        },
+        /// Talking to the site failed.
+        Network { url: String, message: String },
+        /// Reading or writing one of the files this tool keeps failed: the
+        /// cache, answers and submissions, a session token file or the
+        /// sources `new` edits.
+        Cache { path: PathBuf, message: String },
+        /// advent.toml can't be read, or the settings or the command line
+        /// ask for something that can't be done, like a profile advent.toml
+        /// doesn't have.
+        Config { message: String },
+        /// Nothing can produce the input (nothing cached, no fixture, no
+        /// session token), as opposed to something that tried and failed.
+        Unavailable { day: usize, reason: String },
+        /// The input was fine but there is no answer in it.
+        NoSolution { day: usize, message: String },
+        /// The input parsed but breaks one of the puzzle's promises, like a
+        /// missing start marker.
+        InvalidInput { day: usize, message: String },
+        /// The runner gave up on the part and the solver noticed.
+        Cancelled { day: usize },
+        /// An answer wasn't submitted, the history already rules it out.
+        Rejected { reason: String },
+    }
+
+    impl AocError {
+        /// A parse error at `at`, a slice of `input`; the line and column
+        /// are worked out from where it sits. If `at` isn't part of `input`
+        /// the location is unknown, so it's reported as line 1 column 1 with
+        /// `at` as the snippet.
+        pub fn parse<S: Into<String>>(day: usize, input: &str, at: &str, message: S) -> AocError {
+            let offset = (at.as_ptr() as usize)
+                .checked_sub(input.as_ptr() as usize)
+                .filter(|&offset| offset + at.len() <= input.len())
+                .filter(|&offset| input.is_char_boundary(offset));
+            let offset = match offset {
+                Some(offset) => offset,
+                None => {
+                    return AocError::Parse {
+                        day,
+                        line: 1,
+                        column: 1,
+                        snippet: at.lines().next().unwrap_or("").to_string(),
+                        message: message.into(),
+                    }
+                }
+            };
+
+            let before = &input[..offset];
+            let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+            AocError::Parse {
+                day,
+                line: before.matches('\n').count() + 1,
+                column: before[line_start..].chars().count() + 1,
+                snippet: input[line_start..].lines().next().unwrap_or("").to_string(),
+                message: message.into(),
+            }
+        }
+
+        /// A parse error from a failed nom parser run over (part of) `input`.
+        pub fn nom(day: usize, input: &str, e: nom::Err<nom::error::Error<&str>>) -> AocError {
+            match e {
+                nom::Err::Error(e) | nom::Err::Failure(e) => {
+                    let message = match e.code {
+                        ErrorKind::Digit => "expected a number".to_string(),
+                        ErrorKind::Tag => "unexpected text".to_string(),
+                        ErrorKind::Eof => "unexpected text at the end".to_string(),
+                        code => format!("invalid input ({:?})", code),
+                    };
+                    AocError::parse(day, input, e.input, message)
+                }
+                nom::Err::Incomplete(_) => {
+                    AocError::parse(day, input, &input[input.len()..], "unexpected end of input")
+                }
+            }
+        }
+
+        pub fn invalid_input<S: Into<String>>(day: usize, message: S) -> AocError {
+            AocError::InvalidInput {
+                day,
+                message: message.into(),
+            }
+        }
+
+        pub fn no_solution<S: Into<String>>(day: usize, message: S) -> AocError {
+            AocError::NoSolution {
+                day,
+                message: message.into(),
+            }
+        }
+
+        pub fn unavailable<S: Into<String>>(day: usize, reason: S) -> AocError {
+            AocError::Unavailable {
+                day,
+                reason: reason.into(),
+            }
+        }
+
+        pub fn cancelled(day: usize) -> AocError {
+            AocError::Cancelled { day }
+        }
+
+        pub fn rejected<S: Into<String>>(reason: S) -> AocError {
+            AocError::Rejected {
+                reason: reason.into(),
+            }
+        }
+
+        pub fn network<S: Into<String>, E: fmt::Display>(url: S, e: E) -> AocError {
+            AocError::Network {
+                url: url.into(),
+                message: e.to_string(),
+            }
+        }
+
+        pub fn cache<P: Into<PathBuf>, E: fmt::Display>(path: P, e: E) -> AocError {
+            AocError::Cache {
+                path: path.into(),
+                message: e.to_string(),
+            }
+        }
+
+        pub fn config<S: Into<String>>(message: S) -> AocError {
+            AocError::Config {
+                message: message.into(),
+            }
+        }
+    }
+
+    impl fmt::Display for AocError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                AocError::Parse {
+                    day,
+                    line,
+                    column,
+                    snippet,
+                    message,
+                } => {
+                    let gutter = line.to_string().len();
+                    writeln!(
+                        f,
+                        "day {} line {} column {}: {}",
+                        day, line, column, message
+                    )?;
+                    writeln!(f, "{} | {}", line, snippet)?;
+                    write!(f, "{:gutter$} | {:>column$}", "", "^")
+                }
+                AocError::Network { url, message } => write!(f, "{}: {}", url, message),
+                AocError::Cache { path, message } => write!(f, "{}: {}", path.display(), message),
+                AocError::Config { message } => write!(f, "{}", message),
+                AocError::Unavailable { day, reason } => {
+                    write!(f, "input for day {} is unavailable: {}", day, reason)
+                }
+                AocError::NoSolution { day, message } => {
+                    write!(f, "day {} has no solution: {}", day, message)
+                }
+                AocError::InvalidInput { day, message } => {
+                    write!(f, "invalid input for day {}: {}", day, message)
+                }
+                AocError::Cancelled { day } => {
+                    write!(f, "day {} was cancelled, it ran out of time", day)
+                }
+                AocError::Rejected { reason } => write!(f, "not submitting: {}", reason),
+            }
+        }
+    }
+
+    impl Error for AocError {}
+}
+
+#[cfg(test)]
+mod test {
+    use nom::bytes::complete::tag;
+    use nom::character::complete;
+    use nom::sequence::preceded;
+
+    use super::error::*;
+
+    #[test]
+    fn parse_location() {
+        let input = "1-2,3-4\n5-6,x-8\n";
+        let at = &input[12..];
+
+        match AocError::parse(4, input, at, "expected a number") {
+            AocError::Parse {
+                day,
+                line,
+                column,
+                snippet,
+                ..
+            } => {
+                assert_eq!((day, line, column), (4, 2, 5));
+                assert_eq!(snippet, "5-6,x-8");
+            }
+            e => panic!("unexpected {:?}", e),
+        }
+    }
+
+    #[test]
+    fn parse_elsewhere() {
+        let input = "1-2,3-4";
+        let copy = "x-8".to_string();
+
+        match AocError::parse(4, input, &copy, "expected a number") {
+            AocError::Parse {
+                line,
+                column,
+                snippet,
+                ..
+            } => {
+                assert_eq!((line, column), (1, 1));
+                assert_eq!(snippet, "x-8");
+            }
+            e => panic!("unexpected {:?}", e),
+        }
+    }
+
+    #[test]
+    fn parse_display() {
+        let input = "1-2,3-4\n5-6,x-8";
+        let e = AocError::parse(4, input, &input[12..], "expected a number");
+
+        assert_eq!(
+            e.to_string(),
+            "day 4 line 2 column 5: expected a number\n2 | 5-6,x-8\n  |     ^"
+        );
+    }
+
+    #[test]
+    fn nom_errors() {
+        let input = "x=1\nx=a";
+        let line = input.lines().nth(1).unwrap();
+        let e =
+            preceded(tag::<_, _, nom::error::Error<&str>>("x="), complete::i32)(line).unwrap_err();
+
+        assert!(AocError::nom(15, input, e)
+            .to_string()
+            .starts_with("day 15 line 2 column 3: expected a number"));
+    }
+}
@@ -0,0 +1,318 @@
+pub mod examples {
+    use crate::client::client::Client;
+    use crate::config::config::Config;
+    use crate::error::error::AocError;
+    use crate::helper::helper::{FileCache, HttpProvider, InputProvider};
+
+    /// An example from a puzzle page along with the answers the page gives.
+    ///
+    /// The answers are the last emphasised `<code><em>` of each part's
+    /// description, which is normally the answer for the first example. Days
+    /// that introduce a new example for part 2 won't line up.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Example {
+        pub input: String,
+        pub answers: [Option<String>; 2],
+    }
+
+    impl Example {
+        pub fn answer(&self, part: u8) -> Option<&String> {
+            self.answers.get(part as usize - 1)?.as_ref()
+        }
+    }
+
+    fn decode_entities(s: &str) -> String {
+        let mut result = String::new();
+        let mut rest = s;
+
+        while let Some(i) = rest.find('&') {
+            result.push_str(&rest[..i]);
+            rest = &rest[i..];
+
+            let end = match rest.find(';') {
+                Some(end) if end <= 8 => end,
+                _ => {
+                    result.push('&');
+                    rest = &rest[1..];
+                    continue;
+                }
+            };
+
+            let decoded = match &rest[1..end] {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                n if n.starts_with("#x") => u32::from_str_radix(&n[2..], 16)
+                    .ok()
+                    .and_then(char::from_u32),
+                n if n.starts_with('#') => n[1..].parse().ok().and_then(char::from_u32),
+                _ => None,
+            };
+
+            match decoded {
+                Some(c) => {
+                    result.push(c);
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    result.push('&');
+                    rest = &rest[1..];
+                }
+            }
+        }
+
+        result.push_str(rest);
+        result
+    }
+
+    fn strip_tags(s: &str) -> String {
+        let mut text = String::new();
+        let mut in_tag = false;
+        for c in s.chars() {
+            match c {
+                '<' => in_tag = true,
+                '>' => in_tag = false,
+                c if !in_tag => text.push(c),
+                _ => {}
+            }
+        }
+        decode_entities(&text)
+    }
+
+    /// Everything between each `open` and the following `close`.
+    fn between<'a>(html: &'a str, open: &'a str, close: &'a str) -> Vec<&'a str> {
+        let mut result = vec![];
+        let mut rest = html;
+
+        while let Some(start) = rest.find(open) {
+            rest = &rest[start + open.len()..];
+            match rest.find(close) {
+                Some(end) => {
+                    result.push(&rest[..end]);
+                    rest = &rest[end + close.len()..];
+                }
+                None => break,
+            }
+        }
+
+        result
+    }
+
+    /// The text of every `<pre><code>` block, in page order.
+    pub fn code_blocks(html: &str) -> Vec<String> {
+        between(html, "<pre><code>", "</code></pre>")
+            .into_iter()
+            .map(strip_tags)
+            .collect()
+    }
+
+    /// The puzzle's name, from the `--- Day 1: Calorie Counting ---`
+    /// heading.
+    pub fn parse_title(html: &str) -> Option<String> {
+        let heading = strip_tags(between(html, "<h2>", "</h2>").first()?);
+        let title = heading.trim().trim_matches('-').trim();
+        let (_, name) = title.split_once(": ")?;
+        Some(name.to_string())
+    }
+
+    /// The last emphasised code of each part's description.
+    pub fn emphasised_answers(html: &str) -> Vec<Option<String>> {
+        between(html, "<article class=\"day-desc\">", "</article>")
+            .into_iter()
+            .map(|article| {
+                let mut found = between(article, "<code><em>", "</em></code>");
+                found.extend(between(article, "<em><code>", "</code></em>"));
+                // whichever appears last in the article wins
+                found
+                    .into_iter()
+                    .max_by_key(|s| s.as_ptr() as usize)
+                    .map(strip_tags)
+            })
+            .collect()
+    }
+
+    pub fn parse_example(html: &str, n: usize) -> Option<Example> {
+        let input = code_blocks(html).into_iter().nth(n)?;
+        let mut answers = emphasised_answers(html).into_iter();
+
+        Some(Example {
+            input,
+            answers: [answers.next().flatten(), answers.next().flatten()],
+        })
+    }
+
+    /// Puzzle pages cached as `.advent/{year}/{day}.html`, downloading anything
+    /// missing. A page fetched before part 1 was solved has no part 2; delete
+    /// it to pick that up.
+    pub fn page_provider(config: &Config) -> FileCache {
+        let http = HttpProvider::new(Client::new(config))
+            .puzzle_pages()
+            .with_token_source(config.token().clone());
+        FileCache::new(config.cache_dir())
+            .with_extension("html")
+            .with_upstream(Box::new(http))
+            .with_profile(config.profile())
+    }
+
+    pub fn example_from(
+        pages: &dyn InputProvider,
+        year: u16,
+        day: usize,
+        n: usize,
+    ) -> Result<Example, AocError> {
+        let html = pages.input(year, day)?;
+        parse_example(&html, n)
+            .ok_or_else(|| AocError::invalid_input(day, format!("the page has no example {}", n)))
+    }
+
+    /// The `n`th example (counting from 0) on the puzzle page for `day`.
+    pub fn example(config: &Config, year: u16, day: usize, n: usize) -> Result<Example, AocError> {
+        example_from(&page_provider(config), year, day, n)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::examples::*;
+    use crate::client::client::Client;
+    use crate::config::config::Config;
+    use crate::helper::helper::{is_unavailable, FileCache, HttpProvider};
+    use crate::solution::solution::SOLUTIONS;
+    use crate::testing::testing::{temp_dir, MockServer};
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<body>
+<main>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2><p>For example:</p>
+<pre><code>1000
+2000
+
+4000
+</code></pre>
+<p>Find the Elf carrying the most, <code>a &lt; b &amp;&amp; c</code>, a total of <code><em>4000</em></code> Calories.</p>
+</article>
+<p>Your puzzle answer was <code>71924</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>[<em>1</em>,2]</code></pre>
+<p>In the example above, this is <code><em>7000</em></code> Calories.</p>
+</article>
+</main>
+</body>
+</html>"#;
+
+    /// Pages whose first example and final emphasised answer don't belong
+    /// together, so there is nothing automatic to check.
+    const IRREGULAR: &[(u16, usize, u8)] = &[
+        // a list of inline examples, the emphasis is on the last of them
+        (2022, 6, 1),
+        (2022, 6, 2),
+        // part 2 introduces a larger example
+        (2022, 9, 2),
+        // the first block is a smaller program, and part 2 answers with a picture
+        (2022, 10, 1),
+        (2022, 10, 2),
+        // the example uses row 10 and a 20x20 area instead of the real constants
+        (2022, 15, 1),
+        (2022, 15, 2),
+    ];
+
+    #[test]
+    fn parse_page() {
+        assert_eq!(
+            code_blocks(PAGE),
+            vec!["1000\n2000\n\n4000\n".to_string(), "[1,2]".to_string()]
+        );
+        assert_eq!(
+            emphasised_answers(PAGE),
+            vec![Some("4000".to_string()), Some("7000".to_string())]
+        );
+
+        let example = parse_example(PAGE, 0).unwrap();
+        assert_eq!(example.input, "1000\n2000\n\n4000\n");
+        assert_eq!(example.answer(1), Some(&"4000".to_string()));
+        assert_eq!(example.answer(2), Some(&"7000".to_string()));
+
+        assert!(parse_example(PAGE, 2).is_none());
+    }
+
+    #[test]
+    fn parse_page_title() {
+        assert_eq!(parse_title(PAGE), Some("Calorie Counting".to_string()));
+        assert_eq!(parse_title("<html></html>"), None);
+    }
+
+    #[test]
+    fn parse_unsolved_page() {
+        let html = PAGE.split("<p>Your puzzle answer").next().unwrap();
+        let example = parse_example(html, 0).unwrap();
+
+        assert_eq!(example.answers, [Some("4000".to_string()), None]);
+    }
+
+    #[test]
+    fn fetch_and_cache_page() {
+        let server = MockServer::start();
+        server.respond("/2022/day/1", 200, PAGE);
+
+        let dir = temp_dir("pages");
+        let http = HttpProvider::new(Client::new(&Config::default()).without_delays())
+            .puzzle_pages()
+            .with_base_url(server.url())
+            .with_token("secret");
+        let pages = FileCache::new(&dir)
+            .with_extension("html")
+            .with_upstream(Box::new(http));
+
+        assert_eq!(
+            example_from(&pages, 2022, 1, 0).unwrap().input,
+            "1000\n2000\n\n4000\n"
+        );
+        assert_eq!(example_from(&pages, 2022, 1, 1).unwrap().input, "[1,2]");
+        assert!(example_from(&pages, 2022, 1, 2).is_err());
+
+        assert_eq!(server.requests().len(), 1);
+        assert!(dir.join("2022").join("1.html").exists());
+    }
+
+    /// Every registered day against the first example of its puzzle page,
+    /// for the pages that are cached; tests never download.
+    #[test]
+    fn examples() {
+        let config = Config::load(None).unwrap();
+        let pages = FileCache::new(config.cache_dir()).with_extension("html");
+
+        for s in SOLUTIONS {
+            let example = match example_from(&pages, s.year(), s.day(), 0) {
+                Ok(example) => example,
+                Err(e) if is_unavailable(&e) => {
+                    println!("skipping: {}", e);
+                    continue;
+                }
+                Err(e) => panic!("day {}: {}", s.day(), e),
+            };
+
+            for part in [1, 2] {
+                if IRREGULAR.contains(&(s.year(), s.day(), part)) {
+                    continue;
+                }
+
+                let expected = match example.answer(part) {
+                    Some(expected) => expected,
+                    None => continue,
+                };
+
+                let answer = s.part(part, example.input.clone()).unwrap();
+                assert_eq!(
+                    &answer.to_string(),
+                    expected,
+                    "day {} part {} example",
+                    s.day(),
+                    part
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,349 @@
+pub mod grid {
+    use std::fmt;
+    use std::ops::{Index, IndexMut};
+
+    use crate::error::error::AocError;
+    use crate::point::point::{Direction, Direction8, Point};
+
+    /// With `(0, 0)` the top left and `y` growing downwards.
+    pub type Pos = Point<usize>;
+
+    /// A rectangle of cells stored row by row in one `Vec`.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Grid<T> {
+        width: usize,
+        height: usize,
+        cells: Vec<T>,
+    }
+
+    impl<T> Grid<T> {
+        /// A grid with every cell worked out by `f` from its position.
+        #[allow(dead_code)]
+        pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Grid<T> {
+            let cells = (0..height)
+                .flat_map(|y| (0..width).map(move |x| Point::new(x, y)))
+                .map(&mut f)
+                .collect();
+
+            Grid {
+                width,
+                height,
+                cells,
+            }
+        }
+
+        /// `None` if the rows aren't all the same length.
+        pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Grid<T>> {
+            let width = rows.first().map_or(0, |row| row.len());
+            if rows.iter().any(|row| row.len() != width) {
+                return None;
+            }
+
+            Some(Grid {
+                width,
+                height: rows.len(),
+                cells: rows.into_iter().flatten().collect(),
+            })
+        }
+
+        /// A grid with a cell per character of `text`, turned into a `T` by
+        /// `f`. A character `f` has no cell for is a parse error saying
+        /// `expected`.
+        pub fn parse(
+            day: usize,
+            text: &str,
+            expected: &str,
+            mut f: impl FnMut(char) -> Option<T>,
+        ) -> Result<Grid<T>, AocError> {
+            let rows: Result<Vec<Vec<T>>, AocError> = text
+                .lines()
+                .map(|line| {
+                    line.char_indices()
+                        .map(|(i, c)| {
+                            f(c).ok_or_else(|| AocError::parse(day, text, &line[i..], expected))
+                        })
+                        .collect()
+                })
+                .collect();
+
+            Grid::from_rows(rows?)
+                .ok_or_else(|| AocError::invalid_input(day, "the rows are different lengths"))
+        }
+
+        pub fn width(&self) -> usize {
+            self.width
+        }
+
+        pub fn height(&self) -> usize {
+            self.height
+        }
+
+        pub fn contains(&self, pos: Pos) -> bool {
+            pos.x < self.width && pos.y < self.height
+        }
+
+        fn index_of(&self, pos: Pos) -> Option<usize> {
+            self.contains(pos).then(|| pos.y * self.width + pos.x)
+        }
+
+        pub fn get(&self, pos: Pos) -> Option<&T> {
+            self.index_of(pos).map(|i| &self.cells[i])
+        }
+
+        pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+            self.index_of(pos).map(|i| &mut self.cells[i])
+        }
+
+        /// Puts `value` at `pos`, giving back what was there, or `Err` with
+        /// `value` if `pos` is outside the grid.
+        #[allow(dead_code)]
+        pub fn set(&mut self, pos: Pos, value: T) -> Result<T, T> {
+            match self.get_mut(pos) {
+                Some(cell) => Ok(std::mem::replace(cell, value)),
+                None => Err(value),
+            }
+        }
+
+        /// The position one `step` away from `pos`, if that's in the grid.
+        pub fn offset(&self, pos: Pos, step: Point<isize>) -> Option<Pos> {
+            let pos = Point::new(
+                pos.x.checked_add_signed(step.x)?,
+                pos.y.checked_add_signed(step.y)?,
+            );
+            self.contains(pos).then_some(pos)
+        }
+
+        /// The up to four positions sharing an edge with `pos`.
+        pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+            Direction::ALL
+                .into_iter()
+                .flat_map(move |d| self.offset(pos, d.step()))
+        }
+
+        /// The up to eight positions touching `pos`, diagonals included.
+        #[allow(dead_code)]
+        pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+            Direction8::ALL
+                .into_iter()
+                .flat_map(move |d| self.offset(pos, d.step()))
+        }
+
+        /// Every position from `pos` (not included) to the edge, going
+        /// `step` at a time.
+        pub fn ray(&self, pos: Pos, step: Point<isize>) -> impl Iterator<Item = Pos> + '_ {
+            std::iter::successors(self.offset(pos, step), move |&p| self.offset(p, step))
+        }
+
+        #[allow(dead_code)]
+        pub fn row(&self, y: usize) -> &[T] {
+            &self.cells[y * self.width..(y + 1) * self.width]
+        }
+
+        pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+            // chunks(0) panics, and a grid without columns has no rows worth
+            // looking at
+            self.cells.chunks(self.width.max(1))
+        }
+
+        #[allow(dead_code)]
+        pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+            assert!(x < self.width, "column {} is outside the grid", x);
+            self.cells.iter().skip(x).step_by(self.width)
+        }
+
+        pub fn positions(&self) -> impl Iterator<Item = Pos> {
+            let width = self.width;
+            (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+        }
+
+        /// Every cell with its position, row by row.
+        #[allow(dead_code)]
+        pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+            self.positions().zip(self.cells.iter())
+        }
+
+        /// Where the first cell, row by row, that matches `f` is.
+        pub fn position(&self, f: impl FnMut(&T) -> bool) -> Option<Pos> {
+            let i = self.cells.iter().position(f)?;
+            Some(Point::new(i % self.width, i / self.width))
+        }
+
+        pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+            Grid {
+                width: self.width,
+                height: self.height,
+                cells: self.cells.iter().map(f).collect(),
+            }
+        }
+
+        /// The grid drawn a character per cell, a line per row.
+        #[allow(dead_code)]
+        pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+            let lines: Vec<String> = self
+                .rows()
+                .map(|row| row.iter().map(&mut f).collect())
+                .collect();
+            lines.join("\n")
+        }
+    }
+
+    // only the tests fill or turn grids so far
+    #[allow(dead_code)]
+    impl<T: Clone> Grid<T> {
+        pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+            Grid {
+                width,
+                height,
+                cells: vec![fill; width * height],
+            }
+        }
+
+        /// Rows become columns: `(x, y)` moves to `(y, x)`.
+        pub fn transpose(&self) -> Grid<T> {
+            Grid::from_fn(self.height, self.width, |p| {
+                self[Point::new(p.y, p.x)].clone()
+            })
+        }
+
+        /// Turned a quarter clockwise, so the left column becomes the top
+        /// row.
+        pub fn rotate_right(&self) -> Grid<T> {
+            Grid::from_fn(self.height, self.width, |p| {
+                self[Point::new(p.y, self.height - 1 - p.x)].clone()
+            })
+        }
+
+        /// Turned a quarter anticlockwise, so the top row becomes the left
+        /// column.
+        pub fn rotate_left(&self) -> Grid<T> {
+            Grid::from_fn(self.height, self.width, |p| {
+                self[Point::new(self.width - 1 - p.y, p.x)].clone()
+            })
+        }
+    }
+
+    impl<T> Index<Pos> for Grid<T> {
+        type Output = T;
+
+        fn index(&self, pos: Pos) -> &T {
+            match self.get(pos) {
+                Some(cell) => cell,
+                None => panic!("{} is outside the {}x{} grid", pos, self.width, self.height),
+            }
+        }
+    }
+
+    impl<T> IndexMut<Pos> for Grid<T> {
+        fn index_mut(&mut self, pos: Pos) -> &mut T {
+            let (width, height) = (self.width, self.height);
+            match self.get_mut(pos) {
+                Some(cell) => cell,
+                None => panic!("{} is outside the {}x{} grid", pos, width, height),
+            }
+        }
+    }
+
+    impl<T: fmt::Display> fmt::Display for Grid<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            for (y, row) in self.rows().enumerate() {
+                if y > 0 {
+                    writeln!(f)?;
+                }
+                for cell in row {
+                    write!(f, "{}", cell)?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::grid::*;
+    use crate::error::error::AocError;
+    use crate::point::point::{Direction, Direction8, Point};
+
+    fn at(x: usize, y: usize) -> Pos {
+        Point::new(x, y)
+    }
+
+    fn digits(text: &str) -> Result<Grid<u32>, AocError> {
+        Grid::parse(1, text, "expected a digit", |c| c.to_digit(10))
+    }
+
+    #[test]
+    fn parses_character_maps() {
+        let grid = digits("123\n456").unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(at(2, 1)), Some(&6));
+        assert_eq!(grid.get(at(3, 0)), None);
+        assert_eq!(grid.position(|&d| d == 5), Some(at(1, 1)));
+
+        let e = digits("123\n4x6").unwrap_err().to_string();
+        assert!(
+            e.starts_with("day 1 line 2 column 2: expected a digit"),
+            "{}",
+            e
+        );
+
+        assert!(matches!(
+            digits("123\n45"),
+            Err(AocError::InvalidInput { .. })
+        ));
+        assert_eq!(digits("").unwrap().height(), 0);
+    }
+
+    #[test]
+    fn sets_inside_the_grid() {
+        let mut grid = Grid::new(2, 2, '.');
+
+        assert_eq!(grid.set(at(1, 0), '#'), Ok('.'));
+        assert_eq!(grid.set(at(2, 0), '#'), Err('#'));
+        grid[at(0, 1)] = 'O';
+
+        assert_eq!(grid.to_string(), ".#\nO.");
+        assert_eq!(grid.render(|&c| if c == '.' { ' ' } else { c }), " #\nO ");
+    }
+
+    #[test]
+    fn neighbors_and_rays() {
+        let grid = digits("123\n456\n789").unwrap();
+
+        let corner: Vec<Pos> = grid.neighbors4(at(0, 0)).collect();
+        assert_eq!(corner, vec![at(1, 0), at(0, 1)]);
+        assert_eq!(grid.neighbors4(at(1, 1)).count(), 4);
+        assert_eq!(grid.neighbors8(at(1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8(at(2, 2)).count(), 3);
+
+        let west: Vec<u32> = grid
+            .ray(at(2, 1), Direction::West.step())
+            .map(|p| grid[p])
+            .collect();
+        assert_eq!(west, vec![5, 4]);
+        assert_eq!(grid.ray(at(0, 0), Direction::North.step()).count(), 0);
+        assert_eq!(grid.ray(at(0, 0), Direction8::SouthEast.step()).count(), 2);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = digits("123\n456").unwrap();
+
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(grid.iter().nth(4), Some((at(1, 1), &5)));
+    }
+
+    #[test]
+    fn turns() {
+        let grid = digits("123\n456").unwrap();
+
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36");
+        assert_eq!(grid.rotate_right().to_string(), "41\n52\n63");
+        assert_eq!(grid.rotate_left().to_string(), "36\n25\n14");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+        assert_eq!(grid.map(|d| d * 2).row(0), &[2, 4, 6]);
+    }
+}
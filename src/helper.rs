@@ -1,96 +1,889 @@
-#[cfg(test)]
 pub mod helper {
-    use reqwest::blocking::Response;
-    use std::error::Error;
-    use std::io::prelude::*;
+    use crate::cache::cache::{problems, Metadata, Problem};
+    use crate::client::client::Client;
+    use crate::config::config::{Config, TokenSource};
+    use crate::error::error::AocError;
+    #[cfg(test)]
+    use crate::solution::solution::Answer;
+    use crate::unlock::unlock::check_unlocked;
+    use std::io::{self, Read};
+    use std::time::SystemTime;
     use std::{
-        env,
-        fs::{self, File},
-        path::PathBuf,
+        fs::{self},
+        path::{Path, PathBuf},
     };
 
-    const SESSION_TOKEN_PATH: &str = ".advent";
-    const LOCAL_CACHE_DIR: &str = ".advent";
+    pub const LOCAL_CACHE_DIR: &str = ".advent";
+    pub const AOC_URL: &str = "https://adventofcode.com";
+
+    /// Everything cached before years were part of the layout is from 2022.
+    pub const LEGACY_YEAR: u16 = 2022;
+
+    pub fn is_unavailable(e: &AocError) -> bool {
+        matches!(e, AocError::Unavailable { .. })
+    }
+
+    pub trait InputProvider {
+        fn input(&self, year: u16, day: usize) -> Result<String, AocError>;
+
+        /// `input`, along with what to remember about where it came from.
+        fn fetch(&self, year: u16, day: usize) -> Result<(String, Metadata), AocError> {
+            let text = self.input(year, day)?;
+            let metadata = Metadata::new(&text, None, None);
+            Ok((text, metadata))
+        }
+    }
+
+    /// Downloads inputs from the advent of code site, or anything that serves
+    /// the same `/{year}/day/{day}/input` paths.
+    pub struct HttpProvider {
+        client: Client,
+        base_url: String,
+        token: TokenSource,
+        resource: &'static str,
+    }
+
+    impl HttpProvider {
+        pub fn new(client: Client) -> HttpProvider {
+            HttpProvider {
+                client,
+                base_url: AOC_URL.to_string(),
+                token: TokenSource::default(),
+                resource: "/input",
+            }
+        }
+
+        /// Downloads the puzzle description page (`/{year}/day/{day}`) instead
+        /// of the input.
+        pub fn puzzle_pages(mut self) -> HttpProvider {
+            self.resource = "";
+            self
+        }
+
+        pub fn with_base_url<S: Into<String>>(mut self, base_url: S) -> HttpProvider {
+            self.base_url = base_url.into();
+            self
+        }
+
+        /// Reads the session token from here instead of `~/.advent`.
+        pub fn with_token_source(mut self, token: TokenSource) -> HttpProvider {
+            self.token = token;
+            self
+        }
+
+        #[cfg(test)]
+        pub fn with_token<S: Into<String>>(self, token: S) -> HttpProvider {
+            self.with_token_source(TokenSource::Value(token.into()))
+        }
+
+        fn token(&self, day: usize) -> Result<String, AocError> {
+            self.token.read().map_err(|e| {
+                AocError::unavailable(day, format!("no session token in {}: {}", self.token, e))
+            })
+        }
+    }
+
+    impl InputProvider for HttpProvider {
+        fn input(&self, year: u16, day: usize) -> Result<String, AocError> {
+            self.fetch(year, day).map(|(text, _)| text)
+        }
+
+        fn fetch(&self, year: u16, day: usize) -> Result<(String, Metadata), AocError> {
+            check_unlocked(year, day, SystemTime::now())?;
+
+            let url = format!("{}/{}/day/{}{}", self.base_url, year, day, self.resource);
+            let page = self.client.get(&url, &self.token(day)?)?;
+            let metadata = Metadata::new(&page.body, Some(page.url), Some(page.status));
+            Ok((page.body, metadata))
+        }
+    }
+
+    /// Reads `{dir}/{year}/{day}.txt`, filling it from `upstream` on a miss.
+    /// What was downloaded is described in `{dir}/{year}/{day}.txt.json`.
+    pub struct FileCache {
+        dir: PathBuf,
+        extension: &'static str,
+        upstream: Option<Box<dyn InputProvider>>,
+        profile: Option<String>,
+    }
+
+    impl FileCache {
+        pub fn new<P: Into<PathBuf>>(dir: P) -> FileCache {
+            FileCache {
+                dir: dir.into(),
+                extension: "txt",
+                upstream: None,
+                profile: None,
+            }
+        }
+
+        /// Caches as `{year}/{day}.{extension}` rather than `{year}/{day}.txt`.
+        pub fn with_extension(mut self, extension: &'static str) -> FileCache {
+            self.extension = extension;
+            self
+        }
+
+        pub fn with_upstream(mut self, upstream: Box<dyn InputProvider>) -> FileCache {
+            self.upstream = Some(upstream);
+            self
+        }
+
+        /// The profile recorded in the metadata of anything downloaded.
+        pub fn with_profile(mut self, profile: Option<&str>) -> FileCache {
+            self.profile = profile.map(|p| p.to_string());
+            self
+        }
+
+        pub fn path(&self, year: u16, day: usize) -> PathBuf {
+            self.dir
+                .join(year.to_string())
+                .join(format!("{}.{}", day, self.extension))
+        }
+
+        pub fn metadata_path(&self, year: u16, day: usize) -> PathBuf {
+            self.dir
+                .join(year.to_string())
+                .join(format!("{}.{}.json", day, self.extension))
+        }
+
+        pub fn metadata(&self, year: u16, day: usize) -> Result<Option<Metadata>, AocError> {
+            let p = self.metadata_path(year, day);
+            if !p.exists() {
+                return Ok(None);
+            }
+            let text = fs::read_to_string(&p).map_err(|e| AocError::cache(&p, e))?;
+            serde_json::from_str(&text)
+                .map(Some)
+                .map_err(|e| AocError::cache(p, e))
+        }
+
+        /// The days of `year` with something cached.
+        pub fn days(&self, year: u16) -> Result<Vec<usize>, AocError> {
+            let dir = self.dir.join(year.to_string());
+            if !dir.exists() {
+                return Ok(vec![]);
+            }
+
+            let entries = fs::read_dir(&dir).map_err(|e| AocError::cache(&dir, e))?;
+            let mut days: Vec<usize> = entries
+                .flatten()
+                .flat_map(|entry| {
+                    let name = entry.file_name();
+                    let (day, extension) = name.to_str()?.split_once('.')?;
+                    match extension == self.extension && is_day(day) {
+                        true => day.parse().ok(),
+                        false => None,
+                    }
+                })
+                .collect();
+            days.sort();
+
+            Ok(days)
+        }
+
+        /// What's wrong with the cached file for `day`, if anything.
+        pub fn check(&self, year: u16, day: usize) -> Result<Vec<Problem>, AocError> {
+            let p = self.path(year, day);
+            let text = fs::read_to_string(&p).map_err(|e| AocError::cache(&p, e))?;
+            let metadata = self.metadata(year, day)?;
+            Ok(problems(&text, metadata.as_ref(), self.extension == "html"))
+        }
+
+        /// Forgets `day`, returning whether there was anything to forget.
+        pub fn clear(&self, year: u16, day: usize) -> Result<bool, AocError> {
+            let mut cleared = false;
+            for p in [self.path(year, day), self.metadata_path(year, day)] {
+                if p.exists() {
+                    fs::remove_file(&p).map_err(|e| AocError::cache(&p, e))?;
+                    cleared = true;
+                }
+            }
+            Ok(cleared)
+        }
 
-    fn get_token() -> Result<String, Box<dyn Error>> {
-        let p = PathBuf::new()
-            .join(env::var("HOME")?)
-            .join(SESSION_TOKEN_PATH);
+        /// Downloads `day` again, keeping what's cached until that worked.
+        pub fn refetch(&self, year: u16, day: usize) -> Result<String, AocError> {
+            let upstream = self.upstream.as_ref().ok_or_else(|| {
+                AocError::unavailable(day, "there is nowhere to download it from")
+            })?;
+
+            let (text, metadata) = upstream.fetch(year, day)?;
+            self.store(year, day, &text, metadata)?;
+
+            Ok(text)
+        }
 
-        fs::read_to_string(p)
-            .map(|s| s.trim().to_string())
-            .map_err(|e| e.into())
+        fn store(
+            &self,
+            year: u16,
+            day: usize,
+            text: &str,
+            metadata: Metadata,
+        ) -> Result<(), AocError> {
+            let p = self.path(year, day);
+            if let Some(dir) = p.parent() {
+                fs::create_dir_all(dir).map_err(|e| AocError::cache(dir, e))?;
+            }
+            fs::write(&p, text).map_err(|e| AocError::cache(&p, e))?;
+
+            let metadata = metadata.with_profile(self.profile.clone());
+            let json = serde_json::to_string_pretty(&metadata).expect("metadata always serializes");
+            let p = self.metadata_path(year, day);
+            fs::write(&p, json).map_err(|e| AocError::cache(p, e))
+        }
     }
 
-    fn cache_path(day: usize) -> PathBuf {
-        PathBuf::new()
-            .join(LOCAL_CACHE_DIR)
-            .join(format!("{}.txt", day))
+    impl InputProvider for FileCache {
+        fn input(&self, year: u16, day: usize) -> Result<String, AocError> {
+            let p = self.path(year, day);
+            if p.exists() {
+                return fs::read_to_string(&p).map_err(|e| AocError::cache(p, e));
+            }
+
+            let upstream = self.upstream.as_ref().ok_or_else(|| {
+                AocError::unavailable(day, format!("{} is not cached", p.display()))
+            })?;
+
+            let (text, metadata) = upstream.fetch(year, day)?;
+            self.store(year, day, &text, metadata)?;
+
+            Ok(text)
+        }
     }
 
-    fn cache_path_debug(day: usize) -> PathBuf {
-        PathBuf::new()
-            .join(LOCAL_CACHE_DIR)
-            .join(format!("{}.debug.txt", day))
+    /// Inputs held in memory, mostly useful for tests.
+    #[cfg(test)]
+    #[derive(Default)]
+    pub struct InMemory {
+        inputs: std::collections::HashMap<(u16, usize), String>,
     }
 
-    fn is_daily_cached(day: usize) -> bool {
-        cache_path(day).exists()
+    #[cfg(test)]
+    impl InMemory {
+        pub fn new() -> InMemory {
+            InMemory::default()
+        }
+
+        pub fn with<S: Into<String>>(mut self, year: u16, day: usize, text: S) -> InMemory {
+            self.inputs.insert((year, day), text.into());
+            self
+        }
     }
 
-    fn cache_daily_args(day: usize, r: Response) -> Result<String, Box<dyn Error>> {
-        let loc = cache_path(day);
-        let text = r.text()?;
-        fs::create_dir_all(LOCAL_CACHE_DIR)?;
-        let mut file = File::create(loc)?;
-        file.write_all(text.as_bytes())?;
+    #[cfg(test)]
+    impl InputProvider for InMemory {
+        fn input(&self, year: u16, day: usize) -> Result<String, AocError> {
+            self.inputs
+                .get(&(year, day))
+                .cloned()
+                .ok_or_else(|| AocError::unavailable(day, "not loaded in memory"))
+        }
+    }
 
-        Ok(text)
+    /// A read-only directory of `{year}/{day}.txt` files, e.g. checked-in
+    /// fixtures, or just `{day}.txt` when they are all from one year. Unlike
+    /// `FileCache` it never fetches or writes anything.
+    pub struct Fixtures {
+        dir: PathBuf,
     }
 
-    fn read_cache_daily_args(day: usize) -> Result<String, Box<dyn Error>> {
-        fs::read_to_string(cache_path(day)).map_err(|e| e.into())
+    impl Fixtures {
+        pub fn new<P: Into<PathBuf>>(dir: P) -> Fixtures {
+            Fixtures { dir: dir.into() }
+        }
     }
 
-    fn read_cache_daily_args_debug(day: usize) -> Result<String, Box<dyn Error>> {
-        let p = cache_path_debug(day);
-        if !p.exists() {
-            fs::copy(cache_path(day), &p)?;
+    impl InputProvider for Fixtures {
+        fn input(&self, year: u16, day: usize) -> Result<String, AocError> {
+            let file = format!("{}.txt", day);
+            let mut p = self.dir.join(year.to_string()).join(&file);
+            if !p.exists() {
+                p = self.dir.join(file);
+            }
+            if !p.exists() {
+                return Err(AocError::unavailable(
+                    day,
+                    format!("no fixture at {}", p.display()),
+                ));
+            }
+            fs::read_to_string(&p).map_err(|e| AocError::cache(p, e))
         }
-        fs::read_to_string(p).map_err(|e| e.into())
     }
 
-    fn query_daily_args(day: usize) -> Result<Response, Box<dyn Error>> {
-        let client = reqwest::blocking::ClientBuilder::new().build()?;
-        let session_header = format!("session={}", get_token()?);
-        let s = &session_header[..];
+    /// The same text whatever the day, e.g. piped in on stdin.
+    pub struct Given {
+        text: String,
+    }
 
-        client
-            .get(format!("https://adventofcode.com/2022/day/{}/input", day))
-            .header("cookie", s)
-            .send()
-            .map_err(|e| e.into())
+    impl Given {
+        pub fn new<S: Into<String>>(text: S) -> Given {
+            Given { text: text.into() }
+        }
     }
 
-    fn get_daily_args(day: usize) -> Result<String, Box<dyn Error>> {
-        if is_daily_cached(day) {
-            return read_cache_daily_args(day);
+    impl InputProvider for Given {
+        fn input(&self, _year: u16, _day: usize) -> Result<String, AocError> {
+            Ok(self.text.clone())
         }
+    }
 
-        let response = query_daily_args(day)?;
+    /// Names are used as file names, so keep them to something portable.
+    pub fn is_variant_name(name: &str) -> bool {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
 
-        cache_daily_args(day, response)
+    /// Hand-made inputs kept next to the real ones as
+    /// `{dir}/{year}/{day}/{name}.txt`, e.g. `.advent/2022/14/edge-case.txt`.
+    pub struct Variants {
+        dir: PathBuf,
     }
 
-    pub fn run_day<T: std::fmt::Display>(day: usize, f: fn(String) -> Result<T, Box<dyn Error>>) {
-        let response = get_daily_args(day).unwrap();
-        let value = f(response);
-        println!("{}", value.unwrap());
+    impl Variants {
+        pub fn new<P: Into<PathBuf>>(dir: P) -> Variants {
+            Variants { dir: dir.into() }
+        }
+
+        fn day_dir(&self, year: u16, day: usize) -> PathBuf {
+            self.dir.join(year.to_string()).join(day.to_string())
+        }
+
+        pub fn path(&self, year: u16, day: usize, name: &str) -> PathBuf {
+            self.day_dir(year, day).join(format!("{}.txt", name))
+        }
+
+        /// The days of `year` that have at least one variant.
+        pub fn days(&self, year: u16) -> Result<Vec<usize>, AocError> {
+            let dir = self.dir.join(year.to_string());
+            if !dir.exists() {
+                return Ok(vec![]);
+            }
+
+            let entries = fs::read_dir(&dir).map_err(|e| AocError::cache(&dir, e))?;
+            let mut days: Vec<usize> = entries
+                .flatten()
+                .filter(|entry| entry.path().is_dir())
+                .flat_map(|entry| entry.file_name().to_str()?.parse().ok())
+                .collect();
+            days.sort();
+
+            Ok(days)
+        }
+
+        pub fn list(&self, year: u16, day: usize) -> Result<Vec<String>, AocError> {
+            let dir = self.day_dir(year, day);
+            if !dir.exists() {
+                return Ok(vec![]);
+            }
+
+            let entries = fs::read_dir(&dir).map_err(|e| AocError::cache(&dir, e))?;
+            let mut names: Vec<String> = entries
+                .flatten()
+                .flat_map(|entry| {
+                    let path = entry.path();
+                    match path.extension() {
+                        Some(ext) if ext == "txt" => Some(path.file_stem()?.to_str()?.to_string()),
+                        _ => None,
+                    }
+                })
+                .collect();
+            names.sort();
+
+            Ok(names)
+        }
+
+        /// Saves a new variant, refusing to overwrite an existing one.
+        pub fn create(
+            &self,
+            year: u16,
+            day: usize,
+            name: &str,
+            text: &str,
+        ) -> Result<PathBuf, AocError> {
+            if !is_variant_name(name) {
+                return Err(AocError::invalid_input(
+                    day,
+                    format!(
+                        "{:?} isn't a valid name, use letters, digits, - and _",
+                        name
+                    ),
+                ));
+            }
+
+            let path = self.path(year, day, name);
+            if path.exists() {
+                return Err(AocError::cache(path, "already exists"));
+            }
+
+            let dir = self.day_dir(year, day);
+            fs::create_dir_all(&dir).map_err(|e| AocError::cache(&dir, e))?;
+            fs::write(&path, text).map_err(|e| AocError::cache(&path, e))?;
+
+            Ok(path)
+        }
+
+        /// The variant called `name` of every day.
+        pub fn named<S: Into<String>>(self, name: S) -> Variant {
+            Variant {
+                variants: self,
+                name: name.into(),
+            }
+        }
     }
 
-    pub fn dbg_day<T: std::fmt::Display>(day: usize, f: fn(String) -> Result<T, Box<dyn Error>>) {
-        let response = read_cache_daily_args_debug(day).unwrap();
-        let value = f(response);
-        println!("{}", value.unwrap());
+    pub struct Variant {
+        variants: Variants,
+        name: String,
+    }
+
+    impl InputProvider for Variant {
+        fn input(&self, year: u16, day: usize) -> Result<String, AocError> {
+            let p = self.variants.path(year, day, &self.name);
+            if !p.exists() {
+                return Err(AocError::unavailable(
+                    day,
+                    format!("no input named {} at {}", self.name, p.display()),
+                ));
+            }
+            fs::read_to_string(&p).map_err(|e| AocError::cache(p, e))
+        }
+    }
+
+    /// What `--input` points at: `-` for stdin, the name of a variant in
+    /// `.advent/{year}/{day}/`, or else the path of a file.
+    pub fn input_provider(
+        config: &Config,
+        input: &str,
+    ) -> Result<Box<dyn InputProvider>, AocError> {
+        match input {
+            "-" => {
+                let mut text = String::new();
+                io::stdin()
+                    .read_to_string(&mut text)
+                    .map_err(|e| AocError::cache("<stdin>", e))?;
+                Ok(Box::new(Given::new(text)))
+            }
+            name if is_variant_name(name) => Ok(Box::new(Variants::new(config.root()).named(name))),
+            path => {
+                let text = fs::read_to_string(path).map_err(|e| AocError::cache(path, e))?;
+                Ok(Box::new(Given::new(text)))
+            }
+        }
+    }
+
+    /// The local cache, downloading anything missing.
+    pub fn default_provider(config: &Config) -> FileCache {
+        let http = HttpProvider::new(Client::new(config)).with_token_source(config.token().clone());
+        FileCache::new(config.cache_dir())
+            .with_upstream(Box::new(http))
+            .with_profile(config.profile())
+    }
+
+    /// The local cache without any way to download.
+    pub fn offline_provider(config: &Config) -> FileCache {
+        FileCache::new(config.cache_dir())
+    }
+
+    /// The local cache, downloading from a stand-in for the real site.
+    pub fn provider_with_base_url<S: Into<String>>(config: &Config, base_url: S) -> FileCache {
+        let http = HttpProvider::new(Client::new(config))
+            .with_base_url(base_url)
+            .with_token_source(config.token().clone());
+        FileCache::new(config.cache_dir())
+            .with_upstream(Box::new(http))
+            .with_profile(config.profile())
+    }
+
+    fn is_day(name: &str) -> bool {
+        matches!(name.parse::<usize>(), Ok(1..=25))
+    }
+
+    /// Whether `dir/name` is something that was kept directly in the cache
+    /// before years were part of the layout.
+    fn is_legacy_entry(dir: &Path, name: &str) -> bool {
+        match name.split_once('.') {
+            None => is_day(name) && dir.join(name).is_dir(),
+            Some((day, extension)) if is_day(day) => {
+                ["txt", "html", "debug.txt"].contains(&extension)
+            }
+            _ => name == "answers.toml" || name == "submissions.json",
+        }
+    }
+
+    /// Moves a cache from before years were part of the layout, with inputs,
+    /// pages, answers and variants directly in `dir`, into
+    /// `dir/{LEGACY_YEAR}/`. Anything that already has a counterpart there
+    /// is left alone. Returns what was moved, and where to.
+    pub fn migrate_legacy_layout(dir: &Path) -> Result<Vec<(PathBuf, PathBuf)>, AocError> {
+        if !dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let year_dir = dir.join(LEGACY_YEAR.to_string());
+        let mut moved = vec![];
+
+        let entries = fs::read_dir(dir).map_err(|e| AocError::cache(dir, e))?;
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let name = match name.to_str() {
+                Some(name) if is_legacy_entry(dir, name) => name,
+                _ => continue,
+            };
+
+            let to = year_dir.join(name);
+            if to.exists() {
+                continue;
+            }
+
+            fs::create_dir_all(&year_dir).map_err(|e| AocError::cache(&year_dir, e))?;
+            match fs::rename(entry.path(), &to) {
+                Ok(()) => moved.push((entry.path(), to)),
+                // another run of this tool moving the same cache got there first
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(AocError::cache(entry.path(), e)),
+            }
+        }
+
+        Ok(moved)
+    }
+
+    /// `migrate_legacy_layout` for everywhere `config` keeps things.
+    pub fn migrate_legacy_cache(config: &Config) -> Result<Vec<(PathBuf, PathBuf)>, AocError> {
+        let mut moved = migrate_legacy_layout(config.root())?;
+        if config.cache_dir() != config.root() {
+            moved.extend(migrate_legacy_layout(config.cache_dir())?);
+        }
+        Ok(moved)
+    }
+
+    /// The configuration tests run with, failing them if it's broken.
+    #[cfg(test)]
+    fn test_config() -> Config {
+        match Config::load(None) {
+            Ok(config) => config,
+            Err(e) => panic!("unable to load the config: {}", e),
+        }
+    }
+
+    /// The `debug` variant of a day, starting out as a copy of the real input.
+    #[cfg(test)]
+    fn read_debug_variant(year: u16, day: usize) -> Result<String, AocError> {
+        let config = test_config();
+        let cache = offline_provider(&config);
+        let variants = Variants::new(config.root());
+        if !variants.path(year, day, "debug").exists() {
+            // pick up edits from before variants, when this was `.advent/{day}.debug.txt`
+            let old = config
+                .cache_dir()
+                .join(year.to_string())
+                .join(format!("{}.debug.txt", day));
+            let text = match fs::read_to_string(old) {
+                Ok(text) => text,
+                Err(_) => cache.input(year, day)?,
+            };
+            variants.create(year, day, "debug", &text)?;
+        }
+        variants.named("debug").input(year, day)
+    }
+
+    #[cfg(test)]
+    fn load_or_skip(day: usize, input: Result<String, AocError>) -> Option<String> {
+        match input {
+            Ok(input) => Some(input),
+            // tests never download, without a cached input there is nothing to run
+            Err(e) if is_unavailable(&e) => {
+                println!("skipping: {}", e);
+                None
+            }
+            Err(e) => panic!("unable to load input for day {}: {}", day, e),
+        }
+    }
+
+    /// Runs a part against the real input and checks it against
+    /// `.advent/{year}/answers.toml`. Set `ADVENT_RECORD=1` to bless the
+    /// result.
+    #[cfg(test)]
+    pub fn run_day<T: Into<Answer>>(
+        year: u16,
+        day: usize,
+        part: u8,
+        f: fn(String) -> Result<T, AocError>,
+    ) {
+        use std::sync::Mutex;
+
+        use crate::answers::answers::{AnswerStore, Verification};
+
+        // tests run in parallel and all share the one answers file
+        static STORE: Mutex<()> = Mutex::new(());
+
+        let config = test_config();
+        let input = match load_or_skip(day, offline_provider(&config).input(year, day)) {
+            Some(input) => input,
+            None => return,
+        };
+
+        let answer: Answer = match f(input) {
+            Ok(answer) => answer.into(),
+            Err(e) => panic!("{}", e),
+        };
+        println!("{}", answer);
+
+        let _guard = STORE.lock().unwrap_or_else(|e| e.into_inner());
+        let mut store = AnswerStore::load_default(&config, year).unwrap();
+
+        if std::env::var("ADVENT_RECORD").is_ok() {
+            store.record(day, part, &answer);
+            store.save().unwrap();
+            return;
+        }
+
+        match store.verify(day, part, &answer) {
+            Verification::Mismatch { expected } => panic!(
+                "day {} part {} answered {} but {} is recorded",
+                day, part, answer, expected
+            ),
+            v => println!("{}", v),
+        }
+    }
+
+    /// Runs against `.advent/{year}/{day}/debug.txt`, a hand-editable copy
+    /// of the input, so nothing is verified.
+    #[cfg(test)]
+    pub fn dbg_day<T: std::fmt::Display>(
+        year: u16,
+        day: usize,
+        f: fn(String) -> Result<T, AocError>,
+    ) {
+        if let Some(input) = load_or_skip(day, read_debug_variant(year, day)) {
+            match f(input) {
+                Ok(answer) => println!("{}", answer),
+                Err(e) => panic!("{}", e),
+            }
+        }
+    }
+
+    /// Prints the raw input for a day.
+    #[cfg(test)]
+    pub fn show_input(year: u16, day: usize) {
+        if let Some(input) = load_or_skip(day, offline_provider(&test_config()).input(year, day)) {
+            println!("{}", input);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::helper::*;
+    use crate::cache::cache::{sha256, Problem};
+    use crate::client::client::Client;
+    use crate::config::config::Config;
+    use crate::testing::testing::{temp_dir, MockServer};
+
+    #[test]
+    fn in_memory() {
+        let p = InMemory::new().with(2022, 1, "1000\n2000");
+
+        assert_eq!(p.input(2022, 1).unwrap(), "1000\n2000");
+        assert!(is_unavailable(&p.input(2022, 2).unwrap_err()));
+        assert!(is_unavailable(&p.input(2021, 1).unwrap_err()));
+    }
+
+    #[test]
+    fn fixtures() {
+        let dir = temp_dir("fixtures");
+        fs::write(dir.join("3.txt"), "abc").unwrap();
+        fs::create_dir_all(dir.join("2021")).unwrap();
+        fs::write(dir.join("2021").join("3.txt"), "def").unwrap();
+
+        let p = Fixtures::new(&dir);
+        assert_eq!(p.input(2022, 3).unwrap(), "abc");
+        assert_eq!(p.input(2021, 3).unwrap(), "def");
+        assert!(is_unavailable(&p.input(2022, 4).unwrap_err()));
+    }
+
+    #[test]
+    fn given() {
+        let p = Given::new("abc");
+
+        assert_eq!(p.input(2022, 1).unwrap(), "abc");
+        assert_eq!(p.input(2015, 25).unwrap(), "abc");
+    }
+
+    #[test]
+    fn variants() {
+        let dir = temp_dir("variants");
+        let variants = Variants::new(&dir);
+
+        assert!(variants.days(2022).unwrap().is_empty());
+        assert!(variants.list(2022, 14).unwrap().is_empty());
+
+        let path = variants
+            .create(2022, 14, "edge-case", "498,4 -> 498,6")
+            .unwrap();
+        assert_eq!(path, dir.join("2022").join("14").join("edge-case.txt"));
+        variants
+            .create(2022, 14, "example1", "503,4 -> 502,4")
+            .unwrap();
+        variants.create(2022, 9, "long_rope", "R 5").unwrap();
+        variants.create(2021, 2, "edge-case", "up 1").unwrap();
+
+        assert!(variants.create(2022, 14, "example1", "again").is_err());
+        assert!(variants.create(2022, 14, "../escape", "").is_err());
+
+        assert_eq!(variants.days(2022).unwrap(), vec![9, 14]);
+        assert_eq!(variants.days(2021).unwrap(), vec![2]);
+        assert_eq!(
+            variants.list(2022, 14).unwrap(),
+            vec!["edge-case", "example1"]
+        );
+
+        let p = variants.named("edge-case");
+        assert_eq!(p.input(2022, 14).unwrap(), "498,4 -> 498,6");
+        assert_eq!(p.input(2021, 2).unwrap(), "up 1");
+        assert!(is_unavailable(&p.input(2022, 9).unwrap_err()));
+    }
+
+    #[test]
+    fn file_cache_without_upstream() {
+        let dir = temp_dir("cache-offline");
+        let p = FileCache::new(&dir);
+
+        assert!(is_unavailable(&p.input(2022, 5).unwrap_err()));
+
+        fs::create_dir_all(dir.join("2022")).unwrap();
+        fs::write(dir.join("2022").join("5.txt"), "cached").unwrap();
+        assert_eq!(p.input(2022, 5).unwrap(), "cached");
+        assert!(is_unavailable(&p.input(2021, 5).unwrap_err()));
+    }
+
+    #[test]
+    fn file_cache_fills_from_upstream() {
+        let dir = temp_dir("cache-fill");
+        let upstream = InMemory::new().with(2022, 6, "fresh");
+        let p = FileCache::new(&dir).with_upstream(Box::new(upstream));
+
+        assert_eq!(p.input(2022, 6).unwrap(), "fresh");
+        assert_eq!(
+            fs::read_to_string(dir.join("2022").join("6.txt")).unwrap(),
+            "fresh"
+        );
+        let metadata = p.metadata(2022, 6).unwrap().unwrap();
+        assert_eq!(metadata.sha256, sha256("fresh"));
+        assert!(p.check(2022, 6).unwrap().is_empty());
+    }
+
+    #[test]
+    fn file_cache_maintenance() {
+        let dir = temp_dir("cache-maintenance");
+        let upstream = InMemory::new().with(2022, 3, "vJrwpWtwJgWr");
+        let p = FileCache::new(&dir)
+            .with_upstream(Box::new(upstream))
+            .with_profile(Some("alice"));
+
+        p.input(2022, 3).unwrap();
+        assert_eq!(p.days(2022).unwrap(), vec![3]);
+        assert_eq!(
+            p.metadata(2022, 3).unwrap().unwrap().profile.as_deref(),
+            Some("alice")
+        );
+
+        // cut short after the download
+        fs::write(p.path(2022, 3), "vJrw").unwrap();
+        let problems = p.check(2022, 3).unwrap();
+        assert!(matches!(problems[..], [Problem::Changed { .. }]));
+
+        assert_eq!(p.refetch(2022, 3).unwrap(), "vJrwpWtwJgWr");
+        assert!(p.check(2022, 3).unwrap().is_empty());
+
+        // cached by hand, or before metadata was kept
+        fs::write(p.path(2022, 4), "").unwrap();
+        assert_eq!(
+            p.check(2022, 4).unwrap(),
+            vec![Problem::Empty, Problem::NoMetadata]
+        );
+        assert_eq!(p.days(2022).unwrap(), vec![3, 4]);
+
+        assert!(p.clear(2022, 3).unwrap());
+        assert!(!p.clear(2022, 3).unwrap());
+        assert!(!p.metadata_path(2022, 3).exists());
+        assert_eq!(p.days(2022).unwrap(), vec![4]);
+    }
+
+    #[test]
+    fn http_against_mock_server() {
+        let server = MockServer::start();
+        server.respond("/2021/day/7/input", 200, "$ cd /\n$ ls\n");
+
+        let dir = temp_dir("cache-http");
+        let http = HttpProvider::new(Client::new(&Config::default()).without_delays())
+            .with_base_url(server.url())
+            .with_token("secret");
+        let p = FileCache::new(&dir).with_upstream(Box::new(http));
+
+        assert_eq!(p.input(2021, 7).unwrap(), "$ cd /\n$ ls\n");
+        // the second read comes from the cache
+        assert_eq!(p.input(2021, 7).unwrap(), "$ cd /\n$ ls\n");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].header("cookie"), Some("session=secret"));
+        assert!(requests[0].body.is_empty());
+    }
+
+    #[test]
+    fn migrate_legacy() {
+        let dir = temp_dir("legacy");
+        for file in [
+            "14.txt",
+            "14.html",
+            "9.debug.txt",
+            "answers.toml",
+            "notes.txt",
+        ] {
+            fs::write(dir.join(file), file).unwrap();
+        }
+        fs::create_dir_all(dir.join("14")).unwrap();
+        fs::write(dir.join("14").join("edge-case.txt"), "498,4").unwrap();
+        fs::create_dir_all(dir.join("profiles")).unwrap();
+        fs::create_dir_all(dir.join("2022")).unwrap();
+        fs::write(dir.join("2022").join("14.html"), "newer").unwrap();
+
+        let moved = migrate_legacy_layout(&dir).unwrap();
+        assert_eq!(moved.len(), 4);
+
+        let year = dir.join("2022");
+        assert_eq!(fs::read_to_string(year.join("14.txt")).unwrap(), "14.txt");
+        assert_eq!(fs::read_to_string(year.join("14.html")).unwrap(), "newer");
+        assert!(year.join("9.debug.txt").exists());
+        assert!(year.join("answers.toml").exists());
+        assert!(year.join("14").join("edge-case.txt").exists());
+        assert!(dir.join("notes.txt").exists());
+        assert!(dir.join("profiles").exists());
+        // nothing left to do the second time around
+        assert!(migrate_legacy_layout(&dir).unwrap().is_empty());
+    }
+
+    #[test]
+    fn error_pages_are_not_cached() {
+        let server = MockServer::start();
+        server.respond(
+            "/2022/day/8/input",
+            400,
+            "Please log in to get your puzzle input.",
+        );
+
+        let dir = temp_dir("cache-error");
+        let http = HttpProvider::new(Client::new(&Config::default()).without_delays())
+            .with_base_url(server.url())
+            .with_token("expired");
+        let p = FileCache::new(&dir).with_upstream(Box::new(http));
+
+        assert!(p.input(2022, 8).is_err());
+        assert!(!p.path(2022, 8).exists());
     }
 }
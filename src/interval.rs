@@ -0,0 +1,268 @@
+pub mod interval {
+    use std::fmt;
+    use std::ops::RangeInclusive;
+
+    use crate::point::point::Coordinate;
+
+    /// Whether a range ending at `end` overlaps or runs straight into one
+    /// starting at `start`. `end + 1` can only overflow when `end` is the
+    /// largest value, and then the first comparison has already decided.
+    fn touches<T: Coordinate>(end: T, start: T) -> bool {
+        end >= start || end + T::ONE == start
+    }
+
+    /// A set of integers kept as the fewest inclusive ranges that cover it,
+    /// so a range of millions takes no more room than a range of one.
+    #[derive(Clone, PartialEq, Eq)]
+    pub struct IntervalSet<T> {
+        /// sorted, and never overlapping or adjacent
+        ranges: Vec<(T, T)>,
+    }
+
+    impl<T> Default for IntervalSet<T> {
+        fn default() -> IntervalSet<T> {
+            IntervalSet { ranges: vec![] }
+        }
+    }
+
+    impl<T: Coordinate> IntervalSet<T> {
+        pub fn new() -> IntervalSet<T> {
+            IntervalSet::default()
+        }
+
+        /// Adds everything in `range`, merging it with whatever it overlaps
+        /// or touches. An empty range adds nothing.
+        pub fn insert(&mut self, range: RangeInclusive<T>) {
+            let (mut start, mut end) = range.into_inner();
+            if start > end {
+                return;
+            }
+
+            let mut ranges = Vec::with_capacity(self.ranges.len() + 1);
+            let mut placed = false;
+            for &(a, b) in &self.ranges {
+                if touches(b, start) && touches(end, a) {
+                    start = start.min(a);
+                    end = end.max(b);
+                } else if b < start {
+                    ranges.push((a, b));
+                } else {
+                    if !placed {
+                        ranges.push((start, end));
+                        placed = true;
+                    }
+                    ranges.push((a, b));
+                }
+            }
+            if !placed {
+                ranges.push((start, end));
+            }
+
+            self.ranges = ranges;
+        }
+
+        /// Takes out everything in `range`, splitting the ranges it cuts
+        /// through.
+        pub fn remove(&mut self, range: RangeInclusive<T>) {
+            let (start, end) = range.into_inner();
+            if start > end {
+                return;
+            }
+
+            let mut ranges = Vec::with_capacity(self.ranges.len() + 1);
+            for &(a, b) in &self.ranges {
+                if b < start || a > end {
+                    ranges.push((a, b));
+                    continue;
+                }
+                if a < start {
+                    ranges.push((a, start - T::ONE));
+                }
+                if b > end {
+                    ranges.push((end + T::ONE, b));
+                }
+            }
+
+            self.ranges = ranges;
+        }
+
+        #[allow(dead_code)]
+        pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+            let mut result = self.clone();
+            for range in other.iter() {
+                result.insert(range);
+            }
+            result
+        }
+
+        pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+            let (mut i, mut j) = (0, 0);
+            let mut ranges = vec![];
+
+            while i < self.ranges.len() && j < other.ranges.len() {
+                let (a1, b1) = self.ranges[i];
+                let (a2, b2) = other.ranges[j];
+
+                let (start, end) = (a1.max(a2), b1.min(b2));
+                if start <= end {
+                    ranges.push((start, end));
+                }
+
+                // whichever ends first can't overlap anything further on
+                if b1 < b2 {
+                    i += 1;
+                } else {
+                    j += 1;
+                }
+            }
+
+            IntervalSet { ranges }
+        }
+
+        /// Everything in `self` that isn't in `other`.
+        pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+            let mut result = self.clone();
+            for range in other.iter() {
+                result.remove(range);
+            }
+            result
+        }
+
+        #[allow(dead_code)]
+        pub fn contains(&self, value: T) -> bool {
+            let i = self.ranges.partition_point(|&(_, b)| b < value);
+            self.ranges.get(i).is_some_and(|&(a, _)| a <= value)
+        }
+
+        /// Whether every value in `range` is in the set.
+        pub fn contains_range(&self, range: &RangeInclusive<T>) -> bool {
+            let (start, end) = (*range.start(), *range.end());
+            if start > end {
+                return true;
+            }
+
+            let i = self.ranges.partition_point(|&(_, b)| b < start);
+            self.ranges
+                .get(i)
+                .is_some_and(|&(a, b)| a <= start && end <= b)
+        }
+
+        /// How many values are in the set. Counted wider than `T`, since
+        /// `i32::MIN..=i32::MAX` holds more values than an `i32` can count;
+        /// only a set of every `u64` or `i64` is too many, and reads as
+        /// `u64::MAX`.
+        pub fn len(&self) -> u64 {
+            let total: i128 = self
+                .ranges
+                .iter()
+                .map(|&(a, b)| b.widen() - a.widen() + 1)
+                .sum();
+            u64::try_from(total).unwrap_or(u64::MAX)
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.ranges.is_empty()
+        }
+
+        /// The ranges that make up the set, in order.
+        pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+            self.ranges.iter().map(|&(a, b)| a..=b)
+        }
+
+        /// The holes between the ranges, in order.
+        #[allow(dead_code)]
+        pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+            self.ranges
+                .windows(2)
+                .map(|w| w[0].1 + T::ONE..=w[1].0 - T::ONE)
+        }
+    }
+
+    impl<T: Coordinate> From<RangeInclusive<T>> for IntervalSet<T> {
+        fn from(range: RangeInclusive<T>) -> IntervalSet<T> {
+            let mut set = IntervalSet::new();
+            set.insert(range);
+            set
+        }
+    }
+
+    impl<T: Coordinate> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+        fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> IntervalSet<T> {
+            let mut set = IntervalSet::new();
+            for range in iter {
+                set.insert(range);
+            }
+            set
+        }
+    }
+
+    impl<T: Coordinate + fmt::Debug> fmt::Debug for IntervalSet<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_set().entries(self.iter()).finish()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::ops::RangeInclusive;
+
+    use super::interval::*;
+
+    fn ranges(set: &IntervalSet<i32>) -> Vec<RangeInclusive<i32>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn coalesces() {
+        let mut set: IntervalSet<i32> = [12..=12, 2..=5, 6..=8, 3..=4, 14..=20]
+            .into_iter()
+            .collect();
+        set.insert(RangeInclusive::new(5, 1));
+
+        // 2..=5 and 6..=8 touch, so they're one range; 5..=1 is empty
+        assert_eq!(ranges(&set), vec![2..=8, 12..=12, 14..=20]);
+        assert_eq!(set.len(), 15);
+        assert_eq!(set.gaps().collect::<Vec<_>>(), vec![9..=11, 13..=13]);
+
+        let mut joined = set.clone();
+        joined.insert(9..=13);
+        assert_eq!(ranges(&joined), vec![2..=20]);
+
+        let empty = IntervalSet::<i32>::new();
+        assert!(empty.is_empty());
+        assert_eq!(empty.len(), 0);
+    }
+
+    #[test]
+    fn removes() {
+        let mut set = IntervalSet::from(0..=10);
+        set.remove(3..=4);
+        set.remove(10..=12);
+        set.remove(-5..=0);
+
+        assert_eq!(ranges(&set), vec![1..=2, 5..=9]);
+        assert!(set.contains(2));
+        assert!(!set.contains(3));
+        assert!(!set.contains(10));
+        assert!(set.contains_range(&(5..=9)));
+        assert!(!set.contains_range(&(2..=5)));
+    }
+
+    #[test]
+    fn set_algebra() {
+        let a: IntervalSet<i32> = [0..=5, 10..=15].into_iter().collect();
+        let b: IntervalSet<i32> = [4..=11, 20..=20].into_iter().collect();
+
+        assert_eq!(ranges(&a.union(&b)), vec![0..=15, 20..=20]);
+        assert_eq!(ranges(&a.intersection(&b)), vec![4..=5, 10..=11]);
+        assert_eq!(ranges(&a.difference(&b)), vec![0..=3, 12..=15]);
+        assert_eq!(ranges(&b.difference(&a)), vec![6..=9, 20..=20]);
+
+        let huge = IntervalSet::from(i32::MIN..=i32::MAX);
+        assert_eq!(ranges(&huge.intersection(&b)), ranges(&b));
+        assert!(huge.contains(i32::MAX));
+        assert_eq!(huge.len(), 1 << 32);
+        assert_eq!(IntervalSet::from(0..=u64::MAX).len(), u64::MAX);
+    }
+}
@@ -0,0 +1,224 @@
+pub mod log {
+    use std::env;
+    use std::fmt;
+    use std::str::FromStr;
+    use std::sync::OnceLock;
+
+    /// How much a message matters, from most to least.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum Level {
+        Off,
+        Error,
+        Warn,
+        Info,
+        Debug,
+        Trace,
+    }
+
+    impl Level {
+        /// The level for `-v` given `count` times: info, debug, then trace.
+        pub fn verbosity(count: u8) -> Level {
+            match count {
+                0 => Level::Warn,
+                1 => Level::Info,
+                2 => Level::Debug,
+                _ => Level::Trace,
+            }
+        }
+    }
+
+    impl FromStr for Level {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Level, String> {
+            match s.to_ascii_lowercase().as_str() {
+                "off" => Ok(Level::Off),
+                "error" => Ok(Level::Error),
+                "warn" => Ok(Level::Warn),
+                "info" => Ok(Level::Info),
+                "debug" => Ok(Level::Debug),
+                "trace" => Ok(Level::Trace),
+                _ => Err(format!(
+                    "{:?} isn't a log level, use off, error, warn, info, debug or trace",
+                    s
+                )),
+            }
+        }
+    }
+
+    impl fmt::Display for Level {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let name = match self {
+                Level::Off => "off",
+                Level::Error => "error",
+                Level::Warn => "warn",
+                Level::Info => "info",
+                Level::Debug => "debug",
+                Level::Trace => "trace",
+            };
+            write!(f, "{}", name)
+        }
+    }
+
+    /// Which messages get written: a level for everything, and levels for
+    /// single modules like `day15`.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Filter {
+        default: Level,
+        targets: Vec<(String, Level)>,
+    }
+
+    impl Default for Filter {
+        fn default() -> Filter {
+            Filter {
+                default: Level::Warn,
+                targets: vec![],
+            }
+        }
+    }
+
+    impl Filter {
+        pub fn new(default: Level) -> Filter {
+            Filter {
+                default,
+                targets: vec![],
+            }
+        }
+
+        /// Adds `day15=trace,day14=debug` to the filter; a bare level like
+        /// `debug` sets the default.
+        pub fn parse(mut self, spec: &str) -> Result<Filter, String> {
+            for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+                match directive.split_once('=') {
+                    Some((target, level)) => {
+                        let level = level.parse()?;
+                        self.targets.retain(|(t, _)| t != target);
+                        self.targets.push((target.to_string(), level));
+                    }
+                    None => self.default = directive.parse()?,
+                }
+            }
+            Ok(self)
+        }
+
+        pub fn level(&self, target: &str) -> Level {
+            self.targets
+                .iter()
+                .find(|(t, _)| t == target)
+                .map_or(self.default, |&(_, level)| level)
+        }
+
+        pub fn enabled(&self, target: &str, level: Level) -> bool {
+            level != Level::Off && level <= self.level(target)
+        }
+    }
+
+    static FILTER: OnceLock<Filter> = OnceLock::new();
+
+    /// Sets the filter for the rest of the process. Only the first call
+    /// counts; until then `$ADVENT_LOG` is used, so tests can turn on a
+    /// day's messages too.
+    pub fn init(filter: Filter) {
+        let _ = FILTER.set(filter);
+    }
+
+    fn filter() -> &'static Filter {
+        FILTER.get_or_init(|| {
+            let spec = env::var("ADVENT_LOG").unwrap_or_default();
+            Filter::default().parse(&spec).unwrap_or_else(|e| {
+                eprintln!("ignoring ADVENT_LOG: {}", e);
+                Filter::default()
+            })
+        })
+    }
+
+    /// `day15` for `advent_2022::day15::day15`, the module a day's messages
+    /// are filtered by.
+    pub fn target(module_path: &str) -> &str {
+        module_path.split("::").nth(1).unwrap_or(module_path)
+    }
+
+    pub fn enabled(level: Level, module_path: &str) -> bool {
+        filter().enabled(target(module_path), level)
+    }
+
+    /// Writes a message to stderr, away from the answers. Use the macros,
+    /// which skip formatting anything that won't be written.
+    pub fn write(level: Level, module_path: &str, args: fmt::Arguments) {
+        eprintln!("[{} {}] {}", level, target(module_path), args);
+    }
+
+    #[macro_export]
+    macro_rules! log_at {
+        ($level:expr, $($arg:tt)+) => {
+            if $crate::log::log::enabled($level, module_path!()) {
+                $crate::log::log::write($level, module_path!(), format_args!($($arg)+));
+            }
+        };
+    }
+
+    #[macro_export]
+    macro_rules! error {
+        ($($arg:tt)+) => { $crate::log_at!($crate::log::log::Level::Error, $($arg)+) };
+    }
+
+    #[macro_export]
+    macro_rules! warn {
+        ($($arg:tt)+) => { $crate::log_at!($crate::log::log::Level::Warn, $($arg)+) };
+    }
+
+    #[macro_export]
+    macro_rules! info {
+        ($($arg:tt)+) => { $crate::log_at!($crate::log::log::Level::Info, $($arg)+) };
+    }
+
+    #[macro_export]
+    macro_rules! debug {
+        ($($arg:tt)+) => { $crate::log_at!($crate::log::log::Level::Debug, $($arg)+) };
+    }
+
+    #[macro_export]
+    macro_rules! trace {
+        ($($arg:tt)+) => { $crate::log_at!($crate::log::log::Level::Trace, $($arg)+) };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::log::*;
+
+    #[test]
+    fn levels() {
+        assert_eq!("TRACE".parse(), Ok(Level::Trace));
+        assert!("loud".parse::<Level>().is_err());
+        assert_eq!(Level::verbosity(0), Level::Warn);
+        assert_eq!(Level::verbosity(2), Level::Debug);
+        assert_eq!(Level::verbosity(9), Level::Trace);
+    }
+
+    #[test]
+    fn filters() {
+        let filter = Filter::new(Level::verbosity(1))
+            .parse("day15=trace, day14=off")
+            .unwrap();
+
+        assert!(filter.enabled("day15", Level::Trace));
+        assert!(filter.enabled("day13", Level::Info));
+        assert!(!filter.enabled("day13", Level::Debug));
+        assert!(!filter.enabled("day14", Level::Error));
+
+        let filter = filter.parse("debug,day15=warn").unwrap();
+        assert!(filter.enabled("day13", Level::Debug));
+        assert!(!filter.enabled("day15", Level::Info));
+
+        assert!(Filter::default().parse("day15=loud").is_err());
+        assert!(!Filter::default().enabled("day15", Level::Info));
+    }
+
+    #[test]
+    fn targets() {
+        assert_eq!(target("advent_2022::day15::day15"), "day15");
+        assert_eq!(target("advent_2022::day15::day15::test"), "day15");
+        assert_eq!(target("advent_2022"), "advent_2022");
+    }
+}
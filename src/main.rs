@@ -1,5 +1,29 @@
+#![allow(clippy::module_inception)]
+
+mod answers;
+mod bench;
+mod cache;
+mod cancel;
+mod client;
+mod config;
+mod error;
+mod examples;
+mod grid;
 mod helper;
+mod interval;
+mod log;
+mod point;
+mod progress;
+mod report;
+mod runner;
+mod scaffold;
+mod search;
+mod solution;
+mod sparse;
+mod submit;
+mod testing;
 mod tree;
+mod unlock;
 
 mod day01;
 mod day02;
@@ -17,4 +41,1075 @@ mod day13;
 mod day14;
 mod day15;
 
-fn main() {}
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use itertools::Itertools;
+
+use crate::answers::answers::AnswerStore;
+use crate::bench::bench::{bench, format_duration, DayBench};
+use crate::client::client::user_agent;
+use crate::config::config::Config;
+use crate::error::error::AocError;
+use crate::examples::examples::{example, page_provider, parse_example, parse_title};
+use crate::helper::helper::{
+    default_provider, input_provider, is_unavailable, migrate_legacy_cache, offline_provider,
+    provider_with_base_url, FileCache, Fixtures, InputProvider, Variants,
+};
+use crate::log::log::{Filter, Level};
+use crate::report::report::{json_lines, junit, summary, PartResult, Status};
+use crate::runner::runner::{run_tasks, Failure, Task};
+use crate::scaffold::scaffold::create;
+use crate::solution::solution::{find, latest_year, solutions, Answer, Solution, SOLUTIONS};
+use crate::submit::submit::{Outcome, Submitter};
+use crate::unlock::unlock::{format_wait, next_unlock, unlock_time, until_unlock};
+
+#[derive(Parser)]
+#[command(name = "advent-2022", about = "Advent of Code solutions")]
+struct Cli {
+    /// the event year, defaults to the latest one with any solutions
+    #[arg(long, global = true)]
+    year: Option<u16>,
+
+    /// use the session token and cache of this profile from advent.toml
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
+    /// show the solutions' diagnostics on stderr, more of them each time
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// log levels for single days, e.g. day15=trace,day14=off, on top of
+    /// $ADVENT_LOG
+    #[arg(long, global = true, value_name = "SPEC")]
+    log: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Args)]
+struct Source {
+    /// read inputs from a directory of `{year}/{day}.txt` (or just `{day}.txt`)
+    /// files instead of the cache
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["offline", "base_url"])]
+    fixtures: Option<PathBuf>,
+
+    /// only use inputs that are already cached, never download
+    #[arg(long, conflicts_with = "base_url")]
+    offline: bool,
+
+    /// download inputs from this server instead of adventofcode.com
+    #[arg(long, value_name = "URL")]
+    base_url: Option<String>,
+}
+
+impl Source {
+    fn provider(self, config: &Config) -> Box<dyn InputProvider> {
+        match self {
+            Source {
+                fixtures: Some(dir),
+                ..
+            } => Box::new(Fixtures::new(dir)),
+            Source { offline: true, .. } => Box::new(offline_provider(config)),
+            Source {
+                base_url: Some(url),
+                ..
+            } => Box::new(provider_with_base_url(config, url)),
+            _ => Box::new(default_provider(config)),
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one or more days and print the answers
+    Run {
+        /// the day to run
+        #[arg(long, required_unless_present = "all", conflicts_with = "all")]
+        day: Option<usize>,
+
+        /// only run this part (1 or 2), defaults to both
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// run every implemented day
+        #[arg(long)]
+        all: bool,
+
+        /// compare answers with .advent/{year}/answers.toml and fail on a mismatch
+        #[arg(long)]
+        verify: bool,
+
+        /// save the answers to .advent/{year}/answers.toml as known good
+        #[arg(long, conflicts_with = "verify")]
+        record: bool,
+
+        /// print answers as text, JSON lines or a JUnit XML report
+        #[arg(long, value_enum, default_value_t = RunFormat::Text)]
+        format: RunFormat,
+
+        /// write the answers here instead of stdout, away from anything the
+        /// solutions print
+        #[arg(long, value_name = "PATH")]
+        output: Option<PathBuf>,
+
+        /// how many parts to run at once; with more than one the answers are
+        /// summarised in a table at the end
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+        jobs: u64,
+
+        /// give up on a part that takes longer than this many seconds,
+        /// instead of the `timeout` from advent.toml; 0 for no limit
+        #[arg(long, value_name = "SECS")]
+        timeout: Option<u64>,
+
+        /// run against a named input from .advent/{year}/{day}/, a file, or - for
+        /// stdin
+        #[arg(
+            long,
+            value_name = "NAME|PATH|-",
+            conflicts_with_all = ["verify", "record", "fixtures", "offline", "base_url"]
+        )]
+        input: Option<String>,
+
+        #[command(flatten)]
+        source: Source,
+    },
+
+    /// Submit an answer, running the solver for it unless one is given
+    Submit {
+        #[arg(long)]
+        day: usize,
+
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// submit this instead of the solver's answer
+        #[arg(long)]
+        answer: Option<String>,
+
+        #[command(flatten)]
+        source: Source,
+    },
+
+    /// Run a day against an example from its puzzle page
+    Example {
+        #[arg(long)]
+        day: usize,
+
+        /// which example block on the page, counting from 0
+        #[arg(long, default_value_t = 0)]
+        n: usize,
+    },
+
+    /// Start a day from the template, registered and with its input and
+    /// first example downloaded
+    New {
+        #[arg(long)]
+        day: usize,
+    },
+
+    /// Wait for a puzzle to unlock, then download its input and run it
+    Wait {
+        /// defaults to the next puzzle to unlock
+        #[arg(long)]
+        day: Option<usize>,
+    },
+
+    /// Time the parse and both parts of one or more days
+    Bench {
+        /// the day to benchmark
+        #[arg(long, required_unless_present = "all", conflicts_with = "all")]
+        day: Option<usize>,
+
+        /// benchmark every implemented day
+        #[arg(long)]
+        all: bool,
+
+        /// how many times to run each part
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+        runs: u64,
+
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+
+        /// write the report here instead of stdout, away from anything the
+        /// solutions print
+        #[arg(long, value_name = "PATH")]
+        output: Option<PathBuf>,
+
+        /// benchmark a named input from .advent/{year}/{day}/, a file, or - for
+        /// stdin
+        #[arg(
+            long,
+            value_name = "NAME|PATH|-",
+            conflicts_with_all = ["fixtures", "offline", "base_url"]
+        )]
+        input: Option<String>,
+
+        #[command(flatten)]
+        source: Source,
+    },
+
+    /// Manage hand-made inputs kept in .advent/{year}/{day}/{name}.txt
+    Inputs {
+        #[command(subcommand)]
+        command: InputsCommand,
+    },
+
+    /// Check, clear or download again what's in the cache
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+
+    /// Show where inputs are cached and where the session token comes from
+    Config,
+
+    /// List the implemented days
+    List,
+}
+
+#[derive(Subcommand)]
+enum InputsCommand {
+    /// List the inputs of one day, or of every day that has some
+    List {
+        #[arg(long)]
+        day: Option<usize>,
+    },
+
+    /// Save a new input, copied from the real input unless told otherwise
+    Create {
+        #[arg(long)]
+        day: usize,
+
+        /// letters, digits, - and _
+        #[arg(long)]
+        name: String,
+
+        /// copy this file instead, or - for stdin
+        #[arg(long, value_name = "PATH|-", conflicts_with = "example")]
+        from: Option<String>,
+
+        /// copy this example from the puzzle page instead, counting from 0
+        #[arg(long, value_name = "N")]
+        example: Option<usize>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheCommand {
+    /// Look for cached inputs and pages that are empty, error pages, or
+    /// changed since they were downloaded
+    Verify,
+
+    /// Forget the cached input and puzzle page of a day
+    Clear {
+        #[arg(long)]
+        day: usize,
+    },
+
+    /// Download a day again, or every day that `verify` complains about
+    Refetch {
+        #[arg(long)]
+        day: Option<usize>,
+
+        /// the puzzle page instead of the input
+        #[arg(long)]
+        page: bool,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Table,
+    Markdown,
+    Json,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum RunFormat {
+    Text,
+    Json,
+    Junit,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Print,
+    Verify,
+    Record,
+}
+
+/// What's printed after an answer, e.g. ` (ok)`.
+fn suffix(status: &Status) -> String {
+    match status {
+        Status::Answered | Status::Failed => String::new(),
+        Status::Mismatch { expected } => format!(" (MISMATCH, expected {})", expected),
+        status => format!(" ({})", status.name()),
+    }
+}
+
+fn print_answer(day: usize, part: u8, answer: &Answer, status: &str) {
+    match answer {
+        // multi-line answers (day 10 part 2) start on their own line
+        Answer::Text(text) if text.contains('\n') => {
+            println!("day {:02} part {}{}:\n{}", day, part, status, text)
+        }
+        _ => println!("day {:02} part {}: {}{}", day, part, answer, status),
+    }
+}
+
+/// How the results of a run are reported: as text while it goes, or as a
+/// summary table, JSON lines or a JUnit report once everything has finished.
+struct Output {
+    format: RunFormat,
+    path: Option<PathBuf>,
+}
+
+impl Output {
+    fn print() -> Output {
+        Output {
+            format: RunFormat::Text,
+            path: None,
+        }
+    }
+
+    fn write(&self, results: &[PartResult]) -> bool {
+        let report = match self.format {
+            RunFormat::Text => summary(results),
+            RunFormat::Json => json_lines(results),
+            RunFormat::Junit => junit(results),
+        };
+
+        match &self.path {
+            Some(path) => match fs::write(path, report) {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!("unable to write {}: {}", path.display(), e);
+                    false
+                }
+            },
+            None => {
+                print!("{}", report);
+                true
+            }
+        }
+    }
+}
+
+/// Which parts run, how many at once and how long each of them gets.
+struct Schedule {
+    parts: Vec<u8>,
+    jobs: usize,
+    timeout: Option<Duration>,
+}
+
+impl Schedule {
+    fn serial() -> Schedule {
+        Schedule {
+            parts: vec![1, 2],
+            jobs: 1,
+            timeout: None,
+        }
+    }
+}
+
+fn run(
+    config: &Config,
+    year: u16,
+    provider: &dyn InputProvider,
+    solutions: Vec<&'static dyn Solution>,
+    mode: Mode,
+    schedule: &Schedule,
+    output: &Output,
+) -> bool {
+    let mut ok = true;
+    // answers are printed as they come when they come in order
+    let live = schedule.jobs <= 1 && output.format == RunFormat::Text && output.path.is_none();
+
+    let mut store = match AnswerStore::load_default(config, year) {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("unable to load recorded answers: {}", e);
+            return false;
+        }
+    };
+
+    // inputs are loaded up front, one at a time, so downloads keep to the
+    // client's pace whatever the number of jobs
+    let mut results: Vec<Option<PartResult>> = vec![];
+    let mut tasks: Vec<(usize, Task)> = vec![];
+    for s in solutions {
+        let day = s.day();
+        let input = match provider.input(year, day) {
+            Ok(text) => Arc::new(text),
+            Err(e) => {
+                if is_unavailable(&e) {
+                    eprintln!("day {:02}: {}", day, e);
+                    eprintln!("  save a session token to ~/.advent or pass --fixtures <DIR>");
+                } else {
+                    eprintln!("day {:02}: unable to load input: {}", day, e);
+                }
+                for &part in &schedule.parts {
+                    let error = format!("unable to load input: {}", e);
+                    results.push(Some(PartResult::failed(year, day, s.title(), part, error)));
+                }
+                ok = false;
+                continue;
+            }
+        };
+
+        for &part in &schedule.parts {
+            let task = Task {
+                solution: s,
+                part,
+                input: input.clone(),
+            };
+            tasks.push((results.len(), task));
+            results.push(None);
+        }
+    }
+
+    let (slots, tasks): (Vec<usize>, Vec<Task>) = tasks.into_iter().unzip();
+    // a progress bar only gets in the way of verifying or of a report, and
+    // isn't much use unless someone is watching
+    let watch = live && mode == Mode::Print && io::stderr().is_terminal();
+    run_tasks(
+        &tasks,
+        schedule.jobs,
+        schedule.timeout,
+        watch,
+        |i, finished| {
+            let Task { solution, part, .. } = tasks[i];
+            let day = solution.day();
+
+            let (answer, status, error) = match finished.result {
+                Ok(answer) => {
+                    let status = match mode {
+                        Mode::Print => Status::Answered,
+                        Mode::Verify => Status::from(store.verify(day, part, &answer)),
+                        Mode::Record => {
+                            store.record(day, part, &answer);
+                            Status::Recorded
+                        }
+                    };
+                    if live {
+                        print_answer(day, part, &answer, &suffix(&status));
+                    }
+                    (Some(answer), status, None)
+                }
+                Err(failure) => {
+                    let (status, error) = match failure {
+                        Failure::Error(e) => (Status::Failed, e),
+                        Failure::Panicked(message) => {
+                            (Status::Panicked, format!("panicked: {}", message))
+                        }
+                        Failure::TimedOut(after) => (
+                            Status::TimedOut,
+                            format!("still running after {}", format_duration(after)),
+                        ),
+                    };
+                    eprintln!("day {:02} part {}: {}", day, part, error);
+                    (None, status, Some(error))
+                }
+            };
+
+            ok &= !status.is_failure();
+            results[slots[i]] = Some(PartResult {
+                year,
+                day,
+                title: solution.title(),
+                part,
+                answer,
+                duration: finished.duration,
+                status,
+                error,
+            });
+        },
+    );
+
+    if mode == Mode::Record {
+        if let Err(e) = store.save() {
+            eprintln!("unable to save recorded answers: {}", e);
+            ok = false;
+        }
+    }
+
+    if !live {
+        let results: Vec<PartResult> = results.into_iter().flatten().collect();
+        ok &= output.write(&results);
+    }
+
+    ok
+}
+
+fn submit(
+    config: &Config,
+    year: u16,
+    day: usize,
+    part: u8,
+    answer: Option<String>,
+    source: Source,
+) -> bool {
+    let mut submitter = Submitter::new(config, year);
+    if let Some(url) = &source.base_url {
+        submitter = submitter.with_base_url(url);
+    }
+
+    let answer = match answer {
+        Some(text) => text.parse().map_or(Answer::Text(text), Answer::Number),
+        None => {
+            let solved = find(year, day)
+                .ok_or_else(|| {
+                    AocError::no_solution(day, format!("nothing is implemented for it in {}", year))
+                })
+                .and_then(|s| s.part(part, source.provider(config).input(year, day)?));
+            match solved {
+                Ok(answer) => answer,
+                Err(e) => {
+                    eprintln!("day {:02} part {}: {}", day, part, e);
+                    return false;
+                }
+            }
+        }
+    };
+
+    match submitter.submit(day, part, &answer) {
+        Ok(outcome) => {
+            println!("day {:02} part {}: {} is {}", day, part, answer, outcome);
+            if outcome != Outcome::Correct {
+                return false;
+            }
+
+            // a correct answer is exactly what `run --verify` wants to check against
+            let recorded = AnswerStore::load_default(config, year).and_then(|mut store| {
+                store.record(day, part, &answer);
+                store.save()
+            });
+            if let Err(e) = recorded {
+                eprintln!("unable to record the answer: {}", e);
+            }
+            true
+        }
+        Err(e) => {
+            eprintln!("day {:02} part {}: {}", day, part, e);
+            false
+        }
+    }
+}
+
+fn run_bench(
+    provider: &dyn InputProvider,
+    solutions: Vec<&dyn Solution>,
+    runs: usize,
+    format: Format,
+    output: Option<PathBuf>,
+) -> bool {
+    let mut ok = true;
+    let mut results: Vec<DayBench> = vec![];
+
+    for s in solutions {
+        let day = s.day();
+        let result = provider
+            .input(s.year(), day)
+            .and_then(|text| bench(s, &text, runs));
+        match result {
+            Ok(result) => results.push(result),
+            Err(e) => {
+                eprintln!("day {:02}: {}", day, e);
+                ok = false;
+            }
+        }
+    }
+
+    let report = match format {
+        Format::Table => bench::bench::table(&results),
+        Format::Markdown => bench::bench::markdown(&results),
+        Format::Json => bench::bench::json(&results),
+    };
+
+    match output {
+        Some(path) => {
+            if let Err(e) = fs::write(&path, report) {
+                eprintln!("unable to write {}: {}", path.display(), e);
+                ok = false;
+            }
+        }
+        None => print!("{}", report),
+    }
+
+    ok
+}
+
+/// `--input` if it was given, otherwise wherever `source` says.
+fn provider(
+    config: &Config,
+    input: Option<String>,
+    source: Source,
+) -> Option<Box<dyn InputProvider>> {
+    match input {
+        Some(input) => match input_provider(config, &input) {
+            Ok(provider) => Some(provider),
+            Err(e) => {
+                eprintln!("{}", e);
+                None
+            }
+        },
+        None => Some(source.provider(config)),
+    }
+}
+
+fn inputs(config: &Config, year: u16, command: InputsCommand) -> bool {
+    let variants = Variants::new(config.root());
+
+    match command {
+        InputsCommand::List { day } => {
+            let days = match day {
+                Some(day) => Ok(vec![day]),
+                None => variants.days(year),
+            };
+
+            let listed = days.and_then(|days| {
+                for day in days {
+                    for name in variants.list(year, day)? {
+                        println!("{:02} {}", day, name);
+                    }
+                }
+                Ok(())
+            });
+
+            match listed {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    false
+                }
+            }
+        }
+
+        InputsCommand::Create {
+            day,
+            name,
+            from,
+            example: n,
+        } => {
+            let text = match (from, n) {
+                (Some(from), _) => input_provider(config, &from).and_then(|p| p.input(year, day)),
+                (None, Some(n)) => example(config, year, day, n).map(|example| example.input),
+                (None, None) => default_provider(config).input(year, day),
+            };
+
+            match text.and_then(|text| variants.create(year, day, &name, &text)) {
+                Ok(path) => {
+                    println!("created {}", path.display());
+                    true
+                }
+                Err(e) => {
+                    eprintln!("{}", e);
+                    false
+                }
+            }
+        }
+    }
+}
+
+/// The days to run: all of `year`, or just `day`.
+fn select(year: u16, day: Option<usize>) -> Option<Vec<&'static dyn Solution>> {
+    let selected = match day {
+        Some(day) => find(year, day).into_iter().collect(),
+        None => solutions(year),
+    };
+
+    if selected.is_empty() {
+        match day {
+            Some(day) => eprintln!("day {} of {} is not implemented", day, year),
+            None => eprintln!("nothing from {} is implemented", year),
+        }
+        return None;
+    }
+    Some(selected)
+}
+
+/// The days of `year` in `cache` that something is wrong with.
+fn suspicious(cache: &FileCache, year: u16) -> Result<Vec<usize>, AocError> {
+    let mut days = vec![];
+    for day in cache.days(year)? {
+        if cache.check(year, day)?.iter().any(|p| p.is_suspicious()) {
+            days.push(day);
+        }
+    }
+    Ok(days)
+}
+
+fn cache(config: &Config, year: u16, command: CacheCommand) -> bool {
+    let inputs = default_provider(config);
+    let pages = page_provider(config);
+    let both = [("input", &inputs), ("page", &pages)];
+
+    let result = match command {
+        CacheCommand::Verify => {
+            let mut ok = true;
+            both.into_iter()
+                .try_for_each(|(what, cache)| {
+                    for day in cache.days(year)? {
+                        let problems = cache.check(year, day)?;
+                        ok &= !problems.iter().any(|p| p.is_suspicious());
+
+                        let status = match problems.is_empty() {
+                            true => "ok".to_string(),
+                            false => problems.iter().map(|p| p.to_string()).join(", "),
+                        };
+                        println!("day {:02} {}: {}", day, what, status);
+                    }
+                    Ok(())
+                })
+                .map(|()| ok)
+        }
+
+        CacheCommand::Clear { day } => both
+            .into_iter()
+            .try_for_each(|(what, cache)| {
+                if cache.clear(year, day)? {
+                    println!("day {:02} {}: cleared", day, what);
+                }
+                Ok(())
+            })
+            .map(|()| true),
+
+        CacheCommand::Refetch { day, page } => {
+            let (what, cache) = match page {
+                true => ("page", &pages),
+                false => ("input", &inputs),
+            };
+            let days = match day {
+                Some(day) => Ok(vec![day]),
+                None => suspicious(cache, year),
+            };
+            days.and_then(|days| {
+                for day in days {
+                    cache.refetch(year, day)?;
+                    println!("day {:02} {}: downloaded again", day, what);
+                }
+                Ok(true)
+            })
+        }
+    };
+
+    match result {
+        Ok(ok) => ok,
+        Err(e) => {
+            eprintln!("{}", e);
+            false
+        }
+    }
+}
+
+fn wait(config: &Config, year: u16, day: Option<usize>) -> bool {
+    let day = match day.or_else(|| next_unlock(year, SystemTime::now())) {
+        Some(day) => day,
+        None => {
+            eprintln!("every puzzle of {} has unlocked", year);
+            return false;
+        }
+    };
+
+    if unlock_time(year, day).is_none() {
+        eprintln!("{} has no puzzle for day {}", year, day);
+        return false;
+    }
+
+    // better to find out now than at midnight
+    let s = match find(year, day) {
+        Some(s) => s,
+        None => {
+            eprintln!("day {} of {} is not implemented", day, year);
+            return false;
+        }
+    };
+
+    if let Some(wait) = until_unlock(year, day, SystemTime::now()) {
+        eprintln!(
+            "day {} of {} unlocks in {}, waiting",
+            day,
+            year,
+            format_wait(wait)
+        );
+    }
+    // short naps, so a suspended machine doesn't oversleep
+    while let Some(wait) = until_unlock(year, day, SystemTime::now()) {
+        thread::sleep(wait.min(Duration::from_secs(60)));
+    }
+
+    run(
+        config,
+        year,
+        &default_provider(config),
+        vec![s],
+        Mode::Print,
+        &Schedule {
+            timeout: config.timeout(),
+            ..Schedule::serial()
+        },
+        &Output::print(),
+    )
+}
+
+fn new_day(config: &Config, year: u16, day: usize) -> bool {
+    if unlock_time(year, day).is_none() {
+        eprintln!("{} has no puzzle for day {}", year, day);
+        return false;
+    }
+    if find(year, day).is_some() {
+        eprintln!("day {} of {} is already implemented", day, year);
+        return false;
+    }
+
+    // the page is only a nicety, a locked day can still be started
+    let page = match page_provider(config).input(year, day) {
+        Ok(page) => Some(page),
+        Err(e) => {
+            eprintln!("day {:02}: no puzzle page: {}", day, e);
+            None
+        }
+    };
+    let title = page
+        .as_deref()
+        .and_then(parse_title)
+        .unwrap_or_else(|| format!("Day {}", day));
+
+    match create(year, day, &title) {
+        Ok(path) => println!("created {} ({})", path.display(), title),
+        Err(e) => {
+            eprintln!("{}", e);
+            return false;
+        }
+    }
+
+    let variants = Variants::new(config.root());
+    let example = page.as_deref().and_then(|page| parse_example(page, 0));
+    if let Some(example) = example {
+        if !variants.path(year, day, "example").exists() {
+            match variants.create(year, day, "example", &example.input) {
+                Ok(path) => println!("saved the first example as {}", path.display()),
+                Err(e) => eprintln!("day {:02}: {}", day, e),
+            }
+        }
+    }
+
+    match default_provider(config).input(year, day) {
+        Ok(_) => println!("downloaded the input"),
+        Err(e) => eprintln!("day {:02}: no input yet: {}", day, e),
+    }
+
+    true
+}
+
+fn run_example(config: &Config, year: u16, day: usize, n: usize) -> bool {
+    let s = match find(year, day) {
+        Some(s) => s,
+        None => {
+            eprintln!("day {} of {} is not implemented", day, year);
+            return false;
+        }
+    };
+
+    let example = match example(config, year, day, n) {
+        Ok(example) => example,
+        Err(e) => {
+            eprintln!("day {:02}: {}", day, e);
+            return false;
+        }
+    };
+
+    let mut ok = true;
+    for part in [1, 2] {
+        match s.part(part, example.input.clone()) {
+            Ok(answer) => {
+                let status = match example.answer(part) {
+                    Some(expected) if *expected == answer.to_string() => " (ok)".to_string(),
+                    Some(expected) => {
+                        ok = false;
+                        format!(" (the page says {})", expected)
+                    }
+                    None => String::new(),
+                };
+                print_answer(day, part, &answer, &status);
+            }
+            Err(e) => {
+                eprintln!("day {:02} part {}: {}", day, part, e);
+                ok = false;
+            }
+        }
+    }
+
+    ok
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let spec = env::var("ADVENT_LOG").unwrap_or_default();
+    let filter = Filter::new(Level::verbosity(cli.verbose))
+        .parse(&spec)
+        .and_then(|filter| filter.parse(cli.log.as_deref().unwrap_or("")));
+    match filter {
+        Ok(filter) => log::log::init(filter),
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let config = match Config::load(cli.profile.as_deref()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match migrate_legacy_cache(&config) {
+        Ok(moved) => {
+            for (from, to) in moved {
+                eprintln!("moved {} to {}", from.display(), to.display());
+            }
+        }
+        Err(e) => {
+            eprintln!("unable to move the cache to the per year layout: {}", e);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let year = cli.year.unwrap_or_else(latest_year);
+
+    let ok = match cli.command {
+        Command::Run {
+            day,
+            part,
+            all,
+            verify,
+            record,
+            format,
+            output,
+            jobs,
+            timeout,
+            input,
+            source,
+        } => {
+            let solutions = match select(year, if all { None } else { day }) {
+                Some(solutions) => solutions,
+                None => return ExitCode::FAILURE,
+            };
+            let parts = part.map_or(vec![1, 2], |p| vec![p]);
+
+            let mode = match (verify, record) {
+                (true, _) => Mode::Verify,
+                (_, true) => Mode::Record,
+                _ => Mode::Print,
+            };
+
+            match provider(&config, input, source) {
+                Some(provider) => {
+                    let schedule = Schedule {
+                        parts,
+                        jobs: jobs as usize,
+                        timeout: match timeout {
+                            Some(0) => None,
+                            Some(secs) => Some(Duration::from_secs(secs)),
+                            None => config.timeout(),
+                        },
+                    };
+                    let output = Output {
+                        format,
+                        path: output,
+                    };
+                    run(
+                        &config,
+                        year,
+                        provider.as_ref(),
+                        solutions,
+                        mode,
+                        &schedule,
+                        &output,
+                    )
+                }
+                None => false,
+            }
+        }
+
+        Command::Submit {
+            day,
+            part,
+            answer,
+            source,
+        } => submit(&config, year, day, part, answer, source),
+
+        Command::Example { day, n } => run_example(&config, year, day, n),
+
+        Command::New { day } => new_day(&config, year, day),
+
+        Command::Wait { day } => wait(&config, year, day),
+
+        Command::Inputs { command } => inputs(&config, year, command),
+
+        Command::Bench {
+            day,
+            all,
+            runs,
+            format,
+            output,
+            input,
+            source,
+        } => {
+            let solutions = match select(year, if all { None } else { day }) {
+                Some(solutions) => solutions,
+                None => return ExitCode::FAILURE,
+            };
+
+            match provider(&config, input, source) {
+                Some(provider) => {
+                    run_bench(provider.as_ref(), solutions, runs as usize, format, output)
+                }
+                None => false,
+            }
+        }
+
+        Command::Cache { command } => cache(&config, year, command),
+
+        Command::Config => {
+            println!("profile: {}", config.profile().unwrap_or("(default)"));
+            println!("profiles: {}", config.profiles().join(", "));
+            println!("cache: {}", config.cache_dir().display());
+            println!("hand-made inputs: {}", config.root().display());
+            println!("session token: {}", config.token());
+            println!("user agent: {}", user_agent(config.contact()));
+            match config.timeout() {
+                Some(timeout) => println!("timeout: {}", format_wait(timeout)),
+                None => println!("timeout: none"),
+            }
+            true
+        }
+
+        Command::List => {
+            for s in SOLUTIONS {
+                println!("{} {:02} {}", s.year(), s.day(), s.title());
+            }
+            true
+        }
+    };
+
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,321 @@
+pub mod point {
+    use std::fmt;
+    use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+    /// The integer types the days use, for `Point`s and `IntervalSet`s.
+    pub trait Coordinate:
+        Copy + Ord + Default + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+    {
+        const ZERO: Self;
+        const ONE: Self;
+
+        /// How far apart `self` and `other` are, never negative.
+        fn distance(self, other: Self) -> Self;
+
+        /// -1, 0 or 1; unsigned types have no -1.
+        fn signum(self) -> Self;
+
+        /// The value as an `i128`, which every coordinate type fits in.
+        fn widen(self) -> i128;
+    }
+
+    macro_rules! signed {
+        ($($t:ty),*) => {$(
+            impl Coordinate for $t {
+                const ZERO: $t = 0;
+                const ONE: $t = 1;
+
+                fn distance(self, other: $t) -> $t {
+                    (self - other).abs()
+                }
+
+                fn signum(self) -> $t {
+                    <$t>::signum(self)
+                }
+
+                fn widen(self) -> i128 {
+                    self as i128
+                }
+            }
+        )*};
+    }
+
+    macro_rules! unsigned {
+        ($($t:ty),*) => {$(
+            impl Coordinate for $t {
+                const ZERO: $t = 0;
+                const ONE: $t = 1;
+
+                fn distance(self, other: $t) -> $t {
+                    self.abs_diff(other)
+                }
+
+                fn signum(self) -> $t {
+                    self.min(1)
+                }
+
+                fn widen(self) -> i128 {
+                    self as i128
+                }
+            }
+        )*};
+    }
+
+    signed!(i32, i64, isize);
+    unsigned!(u32, u64, usize);
+
+    /// A position, or the step between two, with `y` growing downwards.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub struct Point<T> {
+        pub x: T,
+        pub y: T,
+    }
+
+    impl<T> Point<T> {
+        pub const fn new(x: T, y: T) -> Point<T> {
+            Point { x, y }
+        }
+
+        /// Both coordinates put through `f`, like `p.map(|v| v as isize)`.
+        pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Point<U> {
+            Point::new(f(self.x), f(self.y))
+        }
+
+        /// The same point as another type, if both coordinates fit.
+        pub fn try_cast<U: TryFrom<T>>(self) -> Option<Point<U>> {
+            Some(Point::new(
+                U::try_from(self.x).ok()?,
+                U::try_from(self.y).ok()?,
+            ))
+        }
+    }
+
+    impl<T: Coordinate> Point<T> {
+        pub const ORIGIN: Point<T> = Point::new(T::ZERO, T::ZERO);
+
+        /// Steps along the axes between the two, the taxicab distance.
+        pub fn manhattan(self, other: Point<T>) -> T {
+            self.x.distance(other.x) + self.y.distance(other.y)
+        }
+
+        /// Steps between the two when diagonals count as one, the king's
+        /// distance.
+        pub fn chebyshev(self, other: Point<T>) -> T {
+            self.x.distance(other.x).max(self.y.distance(other.y))
+        }
+
+        /// Each coordinate as -1, 0 or 1, the single step in this direction.
+        pub fn signum(self) -> Point<T> {
+            self.map(T::signum)
+        }
+
+        /// One step, diagonals included, from `self` towards `other`;
+        /// `self` again when they're the same.
+        pub fn step_toward(self, other: Point<T>) -> Point<T> {
+            let step = |from: T, to: T| match from.cmp(&to) {
+                std::cmp::Ordering::Less => from + T::ONE,
+                std::cmp::Ordering::Equal => from,
+                std::cmp::Ordering::Greater => from - T::ONE,
+            };
+            Point::new(step(self.x, other.x), step(self.y, other.y))
+        }
+    }
+
+    impl<T: Add<Output = T>> Add for Point<T> {
+        type Output = Point<T>;
+
+        fn add(self, other: Point<T>) -> Point<T> {
+            Point::new(self.x + other.x, self.y + other.y)
+        }
+    }
+
+    impl<T: Sub<Output = T>> Sub for Point<T> {
+        type Output = Point<T>;
+
+        fn sub(self, other: Point<T>) -> Point<T> {
+            Point::new(self.x - other.x, self.y - other.y)
+        }
+    }
+
+    impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+        type Output = Point<T>;
+
+        fn mul(self, scale: T) -> Point<T> {
+            Point::new(self.x * scale, self.y * scale)
+        }
+    }
+
+    impl<T: Neg<Output = T>> Neg for Point<T> {
+        type Output = Point<T>;
+
+        fn neg(self) -> Point<T> {
+            Point::new(-self.x, -self.y)
+        }
+    }
+
+    impl<T: AddAssign> AddAssign for Point<T> {
+        fn add_assign(&mut self, other: Point<T>) {
+            self.x += other.x;
+            self.y += other.y;
+        }
+    }
+
+    impl<T: SubAssign> SubAssign for Point<T> {
+        fn sub_assign(&mut self, other: Point<T>) {
+            self.x -= other.x;
+            self.y -= other.y;
+        }
+    }
+
+    impl<T> From<(T, T)> for Point<T> {
+        fn from((x, y): (T, T)) -> Point<T> {
+            Point::new(x, y)
+        }
+    }
+
+    impl<T> From<Point<T>> for (T, T) {
+        fn from(p: Point<T>) -> (T, T) {
+            (p.x, p.y)
+        }
+    }
+
+    impl<T: fmt::Display> fmt::Display for Point<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{},{}", self.x, self.y)
+        }
+    }
+
+    /// The four ways to step to a cell sharing an edge.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Direction {
+        North,
+        East,
+        South,
+        West,
+    }
+
+    impl Direction {
+        /// Clockwise from north.
+        pub const ALL: [Direction; 4] = [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ];
+
+        pub fn step(self) -> Point<isize> {
+            Direction8::from(self).step()
+        }
+    }
+
+    /// The eight ways to step to a touching cell, diagonals included.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Direction8 {
+        North,
+        NorthEast,
+        East,
+        SouthEast,
+        South,
+        SouthWest,
+        West,
+        NorthWest,
+    }
+
+    impl Direction8 {
+        /// Clockwise from north.
+        pub const ALL: [Direction8; 8] = [
+            Direction8::North,
+            Direction8::NorthEast,
+            Direction8::East,
+            Direction8::SouthEast,
+            Direction8::South,
+            Direction8::SouthWest,
+            Direction8::West,
+            Direction8::NorthWest,
+        ];
+
+        pub fn step(self) -> Point<isize> {
+            let (x, y) = match self {
+                Direction8::North => (0, -1),
+                Direction8::NorthEast => (1, -1),
+                Direction8::East => (1, 0),
+                Direction8::SouthEast => (1, 1),
+                Direction8::South => (0, 1),
+                Direction8::SouthWest => (-1, 1),
+                Direction8::West => (-1, 0),
+                Direction8::NorthWest => (-1, -1),
+            };
+            Point::new(x, y)
+        }
+    }
+
+    impl From<Direction> for Direction8 {
+        fn from(d: Direction) -> Direction8 {
+            match d {
+                Direction::North => Direction8::North,
+                Direction::East => Direction8::East,
+                Direction::South => Direction8::South,
+                Direction::West => Direction8::West,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::point::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point::new(3, -2);
+        let b = Point::new(-1, 4);
+
+        assert_eq!(a + b, Point::new(2, 2));
+        assert_eq!(a - b, Point::new(4, -6));
+        assert_eq!(a * 2, Point::new(6, -4));
+        assert_eq!(-a, Point::new(-3, 2));
+
+        let mut c = a;
+        c += b;
+        c -= Point::new(1, 1);
+        assert_eq!(c, Point::new(1, 1));
+    }
+
+    #[test]
+    fn metrics() {
+        let a = Point::new(2, 18);
+        let b = Point::new(-2, 15);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+
+        let u = Point::new(3_usize, 1);
+        assert_eq!(u.manhattan(Point::ORIGIN), 4);
+        assert_eq!(Point::<usize>::ORIGIN.chebyshev(u), 3);
+    }
+
+    #[test]
+    fn steps() {
+        assert_eq!(Point::new(-5, 0).signum(), Point::new(-1, 0));
+        assert_eq!(
+            Point::new(0, 0).step_toward(Point::new(2, -7)),
+            Point::new(1, -1)
+        );
+        assert_eq!(
+            Point::new(3_usize, 3).step_toward(Point::new(3, 0)),
+            Point::new(3, 2)
+        );
+
+        assert_eq!(Direction::West.step(), Point::new(-1, 0));
+        assert_eq!(Direction8::SouthWest.step(), Point::new(-1, 1));
+    }
+
+    #[test]
+    fn conversions() {
+        let p: Point<i32> = (1, 2).into();
+        assert_eq!(<(i32, i32)>::from(p), (1, 2));
+        assert_eq!(p.try_cast::<usize>(), Some(Point::new(1, 2)));
+        assert_eq!(Point::new(-1, 2).try_cast::<usize>(), None);
+        assert_eq!(p.map(|v| v as isize), Point::new(1_isize, 2));
+        assert_eq!(p.to_string(), "1,2");
+    }
+}
@@ -0,0 +1,156 @@
+pub mod progress {
+    use std::cell::RefCell;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// How far along a part is: the phase it's in and how many of that
+    /// phase's steps are done. Solvers report into it with `phase` and
+    /// `step`, the runner reads it to draw a progress bar.
+    #[derive(Debug, Default)]
+    pub struct Progress {
+        phase: Mutex<String>,
+        done: AtomicU64,
+        total: AtomicU64,
+    }
+
+    /// A copy of a `Progress` at one moment.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Snapshot {
+        pub phase: String,
+        pub done: u64,
+        /// 0 when the phase didn't say how many steps it has
+        pub total: u64,
+    }
+
+    impl Progress {
+        pub fn new() -> Progress {
+            Progress::default()
+        }
+
+        pub fn snapshot(&self) -> Snapshot {
+            Snapshot {
+                phase: self.phase.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+                done: self.done.load(Ordering::Relaxed),
+                total: self.total.load(Ordering::Relaxed),
+            }
+        }
+    }
+
+    thread_local! {
+        static CURRENT: RefCell<Option<Arc<Progress>>> = const { RefCell::new(None) };
+    }
+
+    /// Puts back whatever was current before, when dropped, so that holds
+    /// even if `f` panics.
+    struct Restore(Option<Arc<Progress>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            CURRENT.with(|current| *current.borrow_mut() = previous);
+        }
+    }
+
+    /// Runs `f` with `progress` as the one `phase` and `step` report to on
+    /// this thread, so solvers report without taking it as an argument.
+    pub fn with_progress<T>(progress: &Arc<Progress>, f: impl FnOnce() -> T) -> T {
+        let _restore = Restore(CURRENT.with(|current| current.replace(Some(progress.clone()))));
+        f()
+    }
+
+    fn report(f: impl FnOnce(&Progress)) {
+        CURRENT.with(|current| {
+            if let Some(progress) = current.borrow().as_ref() {
+                f(progress)
+            }
+        })
+    }
+
+    /// Starts a new phase of `total` steps, or an unknown number with 0.
+    /// Nothing happens unless the runner is watching.
+    pub fn phase(name: &str, total: u64) {
+        report(|p| {
+            *p.phase.lock().unwrap_or_else(|e| e.into_inner()) = name.to_string();
+            p.done.store(0, Ordering::Relaxed);
+            p.total.store(total, Ordering::Relaxed);
+        })
+    }
+
+    /// One more step of the current phase done.
+    pub fn step() {
+        report(|p| {
+            p.done.fetch_add(1, Ordering::Relaxed);
+        })
+    }
+
+    /// `scanning rows [#####...............]  25% 1000000/4000000`, fitting
+    /// the bar into `width` characters.
+    pub fn bar(snapshot: &Snapshot, width: usize) -> String {
+        if snapshot.total == 0 {
+            return format!("{} {}", snapshot.phase, snapshot.done);
+        }
+
+        let fraction = (snapshot.done as f64 / snapshot.total as f64).min(1.0);
+        let filled = (fraction * width as f64) as usize;
+        format!(
+            "{} [{}{}] {:>3}% {}/{}",
+            snapshot.phase,
+            "#".repeat(filled),
+            ".".repeat(width - filled),
+            (fraction * 100.0) as u64,
+            snapshot.done,
+            snapshot.total
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use super::progress::*;
+
+    #[test]
+    fn reports_to_the_current_progress() {
+        // nobody watching
+        phase("ignored", 10);
+        step();
+
+        let progress = Arc::new(Progress::new());
+        with_progress(&progress, || {
+            phase("scanning rows", 4);
+            step();
+            step();
+        });
+        step();
+
+        let snapshot = progress.snapshot();
+        assert_eq!(
+            snapshot,
+            Snapshot {
+                phase: "scanning rows".to_string(),
+                done: 2,
+                total: 4,
+            }
+        );
+        assert_eq!(bar(&snapshot, 10), "scanning rows [#####.....]  50% 2/4");
+
+        with_progress(&progress, || phase("merging", 0));
+        assert_eq!(bar(&progress.snapshot(), 10), "merging 0");
+    }
+
+    #[test]
+    fn stops_reporting_after_a_panic() {
+        let progress = Arc::new(Progress::new());
+        let panicked = std::panic::catch_unwind(|| {
+            with_progress(&progress, || {
+                phase("doomed", 2);
+                panic!("the solver gave up");
+            })
+        });
+        assert!(panicked.is_err());
+
+        step();
+        assert_eq!(progress.snapshot().done, 0);
+    }
+}
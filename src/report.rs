@@ -0,0 +1,399 @@
+pub mod report {
+    use std::fmt::Write;
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use crate::answers::answers::Verification;
+    use crate::bench::bench::format_duration;
+    use crate::solution::solution::Answer;
+
+    /// What became of one part.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Status {
+        /// answered, with nothing to compare against
+        Answered,
+        Ok,
+        Mismatch {
+            expected: String,
+        },
+        Unrecorded,
+        Recorded,
+        /// the input couldn't be loaded or the solution returned an error
+        Failed,
+        Panicked,
+        TimedOut,
+    }
+
+    impl Status {
+        pub fn name(&self) -> &'static str {
+            match self {
+                Status::Answered => "answered",
+                Status::Ok => "ok",
+                Status::Mismatch { .. } => "mismatch",
+                Status::Unrecorded => "unrecorded",
+                Status::Recorded => "recorded",
+                Status::Failed => "failed",
+                Status::Panicked => "panicked",
+                Status::TimedOut => "timeout",
+            }
+        }
+
+        pub fn is_failure(&self) -> bool {
+            !matches!(
+                self,
+                Status::Answered | Status::Ok | Status::Unrecorded | Status::Recorded
+            )
+        }
+    }
+
+    impl From<Verification> for Status {
+        fn from(v: Verification) -> Status {
+            match v {
+                Verification::Match => Status::Ok,
+                Verification::Mismatch { expected } => Status::Mismatch { expected },
+                Verification::Unrecorded => Status::Unrecorded,
+            }
+        }
+    }
+
+    /// The outcome of running one part of one day.
+    #[derive(Debug, Clone)]
+    pub struct PartResult {
+        pub year: u16,
+        pub day: usize,
+        pub title: &'static str,
+        pub part: u8,
+        pub answer: Option<Answer>,
+        pub duration: Duration,
+        pub status: Status,
+        pub error: Option<String>,
+    }
+
+    impl PartResult {
+        pub fn failed(
+            year: u16,
+            day: usize,
+            title: &'static str,
+            part: u8,
+            error: String,
+        ) -> PartResult {
+            PartResult {
+                year,
+                day,
+                title,
+                part,
+                answer: None,
+                duration: Duration::ZERO,
+                status: Status::Failed,
+                error: Some(error),
+            }
+        }
+    }
+
+    /// A table of every part, with multi-line answers written out after it
+    /// and a count of each status at the end.
+    pub fn summary(results: &[PartResult]) -> String {
+        const HEADER: [&str; 6] = ["day", "title", "part", "answer", "time", "result"];
+
+        let rows: Vec<[String; 6]> = results
+            .iter()
+            .map(|r| {
+                let answer = match &r.answer {
+                    Some(Answer::Text(text)) if text.contains('\n') => "(below)".to_string(),
+                    Some(answer) => answer.to_string(),
+                    None => "-".to_string(),
+                };
+                let result = match (&r.status, &r.error) {
+                    (Status::Mismatch { expected }, _) => {
+                        format!("MISMATCH, expected {}", expected)
+                    }
+                    (status, Some(error)) => format!("{}: {}", status.name(), error),
+                    (status, None) => status.name().to_string(),
+                };
+                [
+                    format!("{:02}", r.day),
+                    r.title.to_string(),
+                    r.part.to_string(),
+                    answer,
+                    format_duration(r.duration),
+                    result,
+                ]
+            })
+            .collect();
+
+        let mut widths = HEADER.map(|h| h.chars().count());
+        for row in &rows {
+            for (w, cell) in widths.iter_mut().zip(row) {
+                *w = (*w).max(cell.chars().count());
+            }
+        }
+
+        let mut out = String::new();
+        let header = HEADER.map(|h| h.to_string());
+        for row in std::iter::once(&header).chain(&rows) {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, w)| format!("{:<w$}", cell, w = w))
+                .collect();
+            writeln!(out, "{}", cells.join("  ").trim_end()).unwrap();
+        }
+
+        for r in results {
+            if let Some(Answer::Text(text)) = &r.answer {
+                if text.contains('\n') {
+                    writeln!(out, "\nday {:02} part {}:\n{}", r.day, r.part, text).unwrap();
+                }
+            }
+        }
+
+        let mut counts: Vec<(&str, usize)> = vec![];
+        for r in results {
+            match counts.iter_mut().find(|(name, _)| *name == r.status.name()) {
+                Some((_, n)) => *n += 1,
+                None => counts.push((r.status.name(), 1)),
+            }
+        }
+        let counts: Vec<String> = counts
+            .iter()
+            .map(|(name, n)| format!("{} {}", n, name))
+            .collect();
+        writeln!(out, "\n{} parts: {}", results.len(), counts.join(", ")).unwrap();
+
+        out
+    }
+
+    /// One JSON object per line, one line per part.
+    pub fn json_lines(results: &[PartResult]) -> String {
+        let mut out = String::new();
+        for r in results {
+            let expected = match &r.status {
+                Status::Mismatch { expected } => Some(expected),
+                _ => None,
+            };
+            let line = json!({
+                "year": r.year,
+                "day": r.day,
+                "title": r.title,
+                "part": r.part,
+                "answer": r.answer.as_ref().map(|a| a.to_string()),
+                "duration_us": r.duration.as_micros() as u64,
+                "status": r.status.name(),
+                "expected": expected,
+                "error": r.error,
+            });
+            writeln!(out, "{}", line).unwrap();
+        }
+        out
+    }
+
+    fn escape(text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        for c in text.chars() {
+            match c {
+                '&' => out.push_str("&amp;"),
+                '<' => out.push_str("&lt;"),
+                '>' => out.push_str("&gt;"),
+                '"' => out.push_str("&quot;"),
+                '\'' => out.push_str("&apos;"),
+                _ => out.push(c),
+            }
+        }
+        out
+    }
+
+    fn seconds(d: Duration) -> String {
+        format!("{:.6}", d.as_secs_f64())
+    }
+
+    /// A JUnit XML report with a suite per year and a test case per part. A
+    /// mismatch is a failure, anything that went wrong is an error.
+    pub fn junit(results: &[PartResult]) -> String {
+        let count =
+            |rs: &[&PartResult], f: fn(&Status) -> bool| rs.iter().filter(|r| f(&r.status)).count();
+        let failures = |s: &Status| matches!(s, Status::Mismatch { .. });
+        let errors = |s: &Status| s.is_failure() && !matches!(s, Status::Mismatch { .. });
+
+        let mut years: Vec<u16> = results.iter().map(|r| r.year).collect();
+        years.dedup();
+
+        let all: Vec<&PartResult> = results.iter().collect();
+        let mut out = String::new();
+        writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+        writeln!(
+            out,
+            r#"<testsuites name="advent" tests="{}" failures="{}" errors="{}" time="{}">"#,
+            all.len(),
+            count(&all, failures),
+            count(&all, errors),
+            seconds(results.iter().map(|r| r.duration).sum())
+        )
+        .unwrap();
+
+        for year in years {
+            let suite: Vec<&PartResult> = results.iter().filter(|r| r.year == year).collect();
+            writeln!(
+                out,
+                r#"  <testsuite name="{}" tests="{}" failures="{}" errors="{}" time="{}">"#,
+                year,
+                suite.len(),
+                count(&suite, failures),
+                count(&suite, errors),
+                seconds(suite.iter().map(|r| r.duration).sum())
+            )
+            .unwrap();
+
+            for r in suite {
+                write!(
+                    out,
+                    r#"    <testcase classname="{}.day{:02}" name="part {}: {}" time="{}""#,
+                    r.year,
+                    r.day,
+                    r.part,
+                    escape(r.title),
+                    seconds(r.duration)
+                )
+                .unwrap();
+
+                let answer = r.answer.as_ref().map(|a| escape(&a.to_string()));
+                let body = match (&r.status, &r.error) {
+                    (Status::Mismatch { expected }, _) => Some(format!(
+                        r#"<failure message="expected {}, got {}"/>"#,
+                        escape(expected),
+                        answer.as_deref().unwrap_or("")
+                    )),
+                    (_, Some(error)) => Some(format!(r#"<error message="{}"/>"#, escape(error))),
+                    _ => None,
+                };
+                let output = answer.map(|a| format!("<system-out>{}</system-out>", a));
+
+                match [body, output].into_iter().flatten().collect::<Vec<_>>() {
+                    children if children.is_empty() => writeln!(out, "/>").unwrap(),
+                    children => {
+                        writeln!(out, ">").unwrap();
+                        for child in children {
+                            writeln!(out, "      {}", child).unwrap();
+                        }
+                        writeln!(out, "    </testcase>").unwrap();
+                    }
+                }
+            }
+
+            writeln!(out, "  </testsuite>").unwrap();
+        }
+
+        writeln!(out, "</testsuites>").unwrap();
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::report::*;
+    use crate::solution::solution::Answer;
+
+    fn results() -> Vec<PartResult> {
+        let result = |part, answer: Answer, status| PartResult {
+            year: 2022,
+            day: 1,
+            title: "Calorie <Counting>",
+            part,
+            answer: Some(answer),
+            duration: Duration::from_micros(1500),
+            status,
+            error: None,
+        };
+
+        vec![
+            result(1, Answer::from(24000), Status::Ok),
+            result(
+                2,
+                Answer::from(45000),
+                Status::Mismatch {
+                    expected: "41000".to_string(),
+                },
+            ),
+            PartResult::failed(
+                2022,
+                2,
+                "Rock Paper Scissors",
+                1,
+                "bad \"input\"".to_string(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn writes_json_lines() {
+        let out = json_lines(&results());
+        let lines: Vec<serde_json::Value> = out
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["day"], 1);
+        assert_eq!(lines[0]["answer"], "24000");
+        assert_eq!(lines[0]["duration_us"], 1500);
+        assert_eq!(lines[0]["status"], "ok");
+        assert_eq!(lines[0]["error"], serde_json::Value::Null);
+        assert_eq!(lines[1]["status"], "mismatch");
+        assert_eq!(lines[1]["expected"], "41000");
+        assert_eq!(lines[2]["status"], "failed");
+        assert_eq!(lines[2]["answer"], serde_json::Value::Null);
+        assert_eq!(lines[2]["error"], "bad \"input\"");
+    }
+
+    #[test]
+    fn writes_junit() {
+        let out = junit(&results());
+
+        assert!(out.contains(r#"<testsuites name="advent" tests="3" failures="1" errors="1""#));
+        assert!(out.contains(
+            r#"<testsuite name="2022" tests="3" failures="1" errors="1" time="0.003000">"#
+        ));
+        assert!(out.contains(
+            r#"<testcase classname="2022.day01" name="part 1: Calorie &lt;Counting&gt;" time="0.001500">"#
+        ));
+        assert!(out.contains(r#"<failure message="expected 41000, got 45000"/>"#));
+        assert!(out.contains(r#"<error message="bad &quot;input&quot;"/>"#));
+        assert!(out.contains("<system-out>24000</system-out>"));
+        assert!(out.trim_end().ends_with("</testsuites>"));
+    }
+
+    #[test]
+    fn writes_summary() {
+        let mut results = results();
+        results.push(PartResult {
+            answer: Some(Answer::Text("#.\n.#".to_string())),
+            status: Status::Answered,
+            ..results[0].clone()
+        });
+        results.push(PartResult {
+            status: Status::TimedOut,
+            error: Some("still running after 1.00s".to_string()),
+            ..PartResult::failed(2022, 3, "Rucksack", 2, String::new())
+        });
+
+        let out = summary(&results);
+        let lines: Vec<&str> = out.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "day  title                part  answer   time    result"
+        );
+        assert_eq!(
+            lines[1],
+            "01   Calorie <Counting>   1     24000    1.50ms  ok"
+        );
+        assert!(lines[2].ends_with("MISMATCH, expected 41000"));
+        assert!(lines[3].ends_with("failed: bad \"input\""));
+        assert!(lines[4].contains("(below)"));
+        assert!(lines[5].ends_with("timeout: still running after 1.00s"));
+        assert!(out.contains("\nday 01 part 1:\n#.\n.#\n"));
+        assert!(out.ends_with("\n5 parts: 1 ok, 1 mismatch, 1 failed, 1 answered, 1 timeout\n"));
+    }
+}
@@ -0,0 +1,293 @@
+pub mod runner {
+    use std::any::Any;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{mpsc, Arc};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use crate::cancel::cancel::{with_token, Token};
+    use crate::progress::progress::{bar, with_progress, Progress};
+    use crate::solution::solution::{Answer, Solution};
+
+    /// One part of one day, with the input to run it against.
+    #[derive(Clone)]
+    pub struct Task {
+        pub solution: &'static dyn Solution,
+        pub part: u8,
+        pub input: Arc<String>,
+    }
+
+    /// Why a task didn't produce an answer.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Failure {
+        Error(String),
+        Panicked(String),
+        /// still running when its time was up
+        TimedOut(Duration),
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct Finished {
+        pub result: Result<Answer, Failure>,
+        pub duration: Duration,
+    }
+
+    fn panic_message(payload: Box<dyn Any + Send>) -> String {
+        match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&str>() {
+                Ok(message) => message.to_string(),
+                Err(_) => "panicked".to_string(),
+            },
+        }
+    }
+
+    fn solve(task: &Task) -> Finished {
+        let start = Instant::now();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            task.solution.part(task.part, task.input.to_string())
+        }));
+
+        Finished {
+            result: match result {
+                Ok(answer) => answer.map_err(|e| Failure::Error(e.to_string())),
+                Err(payload) => Err(Failure::Panicked(panic_message(payload))),
+            },
+            duration: start.elapsed(),
+        }
+    }
+
+    /// How often a progress bar is redrawn.
+    const REDRAW: Duration = Duration::from_millis(100);
+
+    fn draw(task: &Task, progress: &Progress) {
+        let snapshot = progress.snapshot();
+        if !snapshot.phase.is_empty() {
+            eprint!(
+                "\r\x1b[Kday {:02} part {}: {}",
+                task.solution.day(),
+                task.part,
+                bar(&snapshot, 30)
+            );
+        }
+    }
+
+    /// Runs `task`, giving up on it after `timeout` and drawing its progress
+    /// on stderr if `watch`. A solver can't be stopped from outside, so one
+    /// that runs over has its token cancelled and is left on its own thread
+    /// to notice, or to finish regardless.
+    pub fn execute(task: &Task, timeout: Option<Duration>, watch: bool) -> Finished {
+        if timeout.is_none() && !watch {
+            return solve(task);
+        }
+
+        let token = Token::new();
+        let progress = Arc::new(Progress::new());
+        let (tx, rx) = mpsc::channel();
+        let (detached, solver_token, solver_progress) =
+            (task.clone(), token.clone(), progress.clone());
+        thread::spawn(move || {
+            let finished = with_token(&solver_token, || {
+                with_progress(&solver_progress, || solve(&detached))
+            });
+            tx.send(finished)
+        });
+
+        let start = Instant::now();
+        let finished = loop {
+            let left = timeout.map(|t| t.saturating_sub(start.elapsed()));
+            let wait = match (left, watch) {
+                (Some(left), true) => left.min(REDRAW),
+                (Some(left), false) => left,
+                (None, _) => REDRAW,
+            };
+
+            match rx.recv_timeout(wait) {
+                Ok(finished) => break finished,
+                Err(_) if left.is_some_and(|left| left <= wait) => {
+                    token.cancel();
+                    let timeout = timeout.expect("only parts with a limit run out of time");
+                    break Finished {
+                        result: Err(Failure::TimedOut(timeout)),
+                        duration: timeout,
+                    };
+                }
+                Err(_) if watch => draw(task, &progress),
+                Err(_) => {}
+            }
+        };
+
+        if watch && !progress.snapshot().phase.is_empty() {
+            eprint!("\r\x1b[K");
+        }
+        finished
+    }
+
+    /// Runs every task on `jobs` threads, calling `done` with each task's
+    /// index as it finishes. With one job the tasks run in order, and show
+    /// their progress if `watch`.
+    pub fn run_tasks(
+        tasks: &[Task],
+        jobs: usize,
+        timeout: Option<Duration>,
+        watch: bool,
+        mut done: impl FnMut(usize, Finished),
+    ) {
+        if jobs <= 1 {
+            for (i, task) in tasks.iter().enumerate() {
+                done(i, execute(task, timeout, watch));
+            }
+            return;
+        }
+
+        let next = AtomicUsize::new(0);
+        let (tx, rx) = mpsc::channel();
+
+        thread::scope(|scope| {
+            for _ in 0..jobs.min(tasks.len()) {
+                let tx = tx.clone();
+                let next = &next;
+                scope.spawn(move || loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    match tasks.get(i) {
+                        Some(task) => {
+                            if tx.send((i, execute(task, timeout, false))).is_err() {
+                                break;
+                            }
+                        }
+                        None => break,
+                    }
+                });
+            }
+            drop(tx);
+
+            for (i, finished) in rx {
+                done(i, finished);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::runner::*;
+    use crate::cancel::cancel::check;
+    use crate::error::error::AocError;
+    use crate::solution::solution::{Answer, Solution};
+
+    /// Set once the looping part 2 notices it was cancelled.
+    static STOPPED: AtomicBool = AtomicBool::new(false);
+
+    /// Part 1 echoes the input's length, part 2 does whatever the input
+    /// says.
+    struct Fake;
+
+    impl Solution for Fake {
+        fn year(&self) -> u16 {
+            2022
+        }
+
+        fn day(&self) -> usize {
+            1
+        }
+
+        fn title(&self) -> &'static str {
+            "Fake"
+        }
+
+        fn part1(&self, text: String) -> Result<Answer, AocError> {
+            Ok(Answer::from(text.len()))
+        }
+
+        fn part2(&self, text: String) -> Result<Answer, AocError> {
+            match text.as_str() {
+                "panic" => panic!("boom"),
+                "sleep" => {
+                    thread::sleep(Duration::from_secs(5));
+                    Ok(Answer::from(0))
+                }
+                "loop" => loop {
+                    if let Err(e) = check(1) {
+                        STOPPED.store(true, Ordering::Relaxed);
+                        return Err(e);
+                    }
+                    thread::sleep(Duration::from_millis(1));
+                },
+                _ => Err(AocError::invalid_input(1, "no")),
+            }
+        }
+    }
+
+    fn task(part: u8, input: &str) -> Task {
+        Task {
+            solution: &Fake,
+            part,
+            input: Arc::new(input.to_string()),
+        }
+    }
+
+    #[test]
+    fn catches_panics_and_timeouts() {
+        assert_eq!(
+            execute(&task(1, "abc"), None, false).result,
+            Ok(Answer::from(3))
+        );
+        assert!(matches!(
+            execute(&task(2, "nope"), None, false).result,
+            Err(Failure::Error(_))
+        ));
+        assert_eq!(
+            execute(&task(2, "panic"), None, false).result,
+            Err(Failure::Panicked("boom".to_string()))
+        );
+
+        let timeout = Duration::from_millis(50);
+        assert_eq!(
+            execute(&task(2, "sleep"), Some(timeout), false).result,
+            Err(Failure::TimedOut(timeout))
+        );
+        assert_eq!(
+            execute(&task(1, "abcd"), Some(Duration::from_secs(5)), false).result,
+            Ok(Answer::from(4))
+        );
+    }
+
+    #[test]
+    fn cancels_what_runs_over() {
+        let timeout = Duration::from_millis(20);
+        assert_eq!(
+            execute(&task(2, "loop"), Some(timeout), false).result,
+            Err(Failure::TimedOut(timeout))
+        );
+
+        for _ in 0..100 {
+            if STOPPED.load(Ordering::Relaxed) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        panic!("the solver kept going after it was cancelled");
+    }
+
+    #[test]
+    fn runs_everything_once() {
+        let tasks: Vec<Task> = (0..20).map(|n| task(1, &"x".repeat(n))).collect();
+
+        for jobs in [1, 4] {
+            let mut answers = vec![None; tasks.len()];
+            run_tasks(&tasks, jobs, None, false, |i, finished| {
+                assert!(answers[i].is_none());
+                answers[i] = Some(finished.result.unwrap());
+            });
+
+            let expected: Vec<_> = (0..20).map(|n| Some(Answer::from(n))).collect();
+            assert_eq!(answers, expected);
+        }
+    }
+}
@@ -0,0 +1,277 @@
+pub mod scaffold {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    use crate::error::error::AocError;
+    use crate::helper::helper::LEGACY_YEAR;
+
+    /// Where the sources this binary was built from live.
+    pub const SRC_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+    fn source(name: &str) -> PathBuf {
+        Path::new(SRC_DIR).join(name)
+    }
+
+    /// `day16` for the crate's original year, `y2023_day16` for the others
+    /// so years don't clash.
+    pub fn module_name(year: u16, day: usize) -> String {
+        match year {
+            LEGACY_YEAR => format!("day{:02}", day),
+            _ => format!("y{}_day{:02}", year, day),
+        }
+    }
+
+    /// `Day16`, or `Y2023Day16`.
+    pub fn struct_name(year: u16, day: usize) -> String {
+        match year {
+            LEGACY_YEAR => format!("Day{:02}", day),
+            _ => format!("Y{}Day{:02}", year, day),
+        }
+    }
+
+    /// The year and day of a registered solution, from its struct name.
+    fn key(name: &str) -> Option<(u16, usize)> {
+        let name = name.trim().trim_start_matches('&');
+        match name.strip_prefix('Y') {
+            Some(rest) => {
+                let (year, day) = rest.split_once("Day")?;
+                Some((year.parse().ok()?, day.parse().ok()?))
+            }
+            None => Some((LEGACY_YEAR, name.strip_prefix("Day")?.parse().ok()?)),
+        }
+    }
+
+    /// Fills in `template.rs` for a day.
+    pub fn generate(template: &str, year: u16, day: usize, title: &str) -> String {
+        let module = format!("pub mod {}", module_name(year, day));
+        let name = struct_name(year, day);
+
+        let code = template
+            .lines()
+            // the comment explaining the template
+            .skip_while(|line| line.starts_with("//"))
+            .map(|line| {
+                let indent = &line[..line.len() - line.trim_start().len()];
+                let trimmed = line.trim_start();
+                if trimmed.starts_with("const YEAR: u16 =") {
+                    format!("{}const YEAR: u16 = {};", indent, year)
+                } else if trimmed.starts_with("const DAY: usize =") {
+                    format!("{}const DAY: usize = {};", indent, day)
+                } else if trimmed == "\"Template\"" {
+                    // the title goes in as it is, even if it says "Template"
+                    format!("{}{:?}", indent, title)
+                } else {
+                    line.replace("pub mod template", &module)
+                        .replace("Template", &name)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        code + "\n"
+    }
+
+    /// Adds `mod {module};` to the list of days in `main.rs`, keeping it
+    /// sorted.
+    pub fn register_module(main: &str, module: &str) -> Result<String, AocError> {
+        let is_day = |line: &str| {
+            line.starts_with("mod day") || (line.starts_with("mod y") && line.contains("_day"))
+        };
+
+        let mut lines: Vec<String> = main.lines().map(|l| l.to_string()).collect();
+        let start = lines
+            .iter()
+            .position(|l| is_day(l))
+            .ok_or_else(|| AocError::cache(source("main.rs"), "there is no list of days"))?;
+        let end = start + lines[start..].iter().take_while(|l| is_day(l)).count();
+
+        let line = format!("mod {};", module);
+        if lines[start..end].contains(&line) {
+            return Err(AocError::cache(
+                source("main.rs"),
+                format!("{} is already there", module),
+            ));
+        }
+        let at = start + lines[start..end].partition_point(|l| *l < line);
+        lines.insert(at, line);
+
+        Ok(lines.join("\n") + "\n")
+    }
+
+    /// Imports `{name}` in `solution.rs` and adds it to `SOLUTIONS` in order
+    /// of year and day.
+    pub fn register_solution(solution: &str, year: u16, day: usize) -> Result<String, AocError> {
+        let (module, name) = (module_name(year, day), struct_name(year, day));
+        let broken = |message: &str| AocError::cache(source("solution.rs"), message);
+
+        let start = solution
+            .find("pub static SOLUTIONS")
+            .and_then(|i| Some(i + solution[i..].find("= &[")? + 4))
+            .ok_or_else(|| broken("there is no SOLUTIONS"))?;
+        let end = start
+            + solution[start..]
+                .find("];")
+                .ok_or_else(|| broken("SOLUTIONS isn't closed"))?;
+
+        let mut entries: Vec<&str> = solution[start..end]
+            .split(',')
+            .map(|e| e.trim())
+            .filter(|e| !e.is_empty())
+            .collect();
+        let entry = format!("&{}", name);
+        if entries.contains(&entry.as_str()) {
+            return Err(broken(&format!("{} is already in SOLUTIONS", name)));
+        }
+        let at = entries.partition_point(|e| key(e) < Some((year, day)));
+        entries.insert(at, &entry);
+
+        let import = format!("    use crate::{0}::{0}::{1};\n", module, name);
+        let after_imports = solution[..start]
+            .rfind("    use crate::")
+            .and_then(|i| Some(i + solution[i..].find('\n')? + 1))
+            .ok_or_else(|| broken("there are no imports"))?;
+
+        Ok(format!(
+            "{}{}{}\n        {},\n    {}",
+            &solution[..after_imports],
+            import,
+            &solution[after_imports..start],
+            entries.join(", "),
+            &solution[end..]
+        ))
+    }
+
+    /// Writes `src/{module}.rs` from the template and registers it,
+    /// returning the new file.
+    pub fn create(year: u16, day: usize, title: &str) -> Result<PathBuf, AocError> {
+        let path = source(&format!("{}.rs", module_name(year, day)));
+        if path.exists() {
+            return Err(AocError::cache(path, "already exists"));
+        }
+
+        let read = |name: &str| {
+            let p = source(name);
+            fs::read_to_string(&p).map_err(|e| AocError::cache(p, e))
+        };
+        let write =
+            |p: PathBuf, text: String| fs::write(&p, text).map_err(|e| AocError::cache(p, e));
+
+        let code = generate(&read("template.rs")?, year, day, title);
+        let main = register_module(&read("main.rs")?, &module_name(year, day))?;
+        let solution = register_solution(&read("solution.rs")?, year, day)?;
+
+        write(path.clone(), code)?;
+        write(source("main.rs"), main)?;
+        write(source("solution.rs"), solution)?;
+
+        // the registry is written on one line, leave it tidy if we can
+        let formatted = Command::new("rustfmt")
+            .args(["--edition", "2021"])
+            .arg(&path)
+            .arg(source("solution.rs"))
+            .status();
+        if !formatted.is_ok_and(|status| status.success()) {
+            eprintln!("unable to run rustfmt, run cargo fmt before committing");
+        }
+
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::scaffold::*;
+    use crate::error::error::AocError;
+
+    const TEMPLATE: &str = include_str!("template.rs");
+    const MAIN: &str = include_str!("main.rs");
+    const SOLUTION: &str = include_str!("solution.rs");
+
+    #[test]
+    fn names() {
+        assert_eq!(module_name(2022, 16), "day16");
+        assert_eq!(struct_name(2022, 3), "Day03");
+        assert_eq!(module_name(2023, 1), "y2023_day01");
+        assert_eq!(struct_name(2023, 1), "Y2023Day01");
+    }
+
+    #[test]
+    fn generates_from_template() {
+        let code = generate(TEMPLATE, 2022, 16, "Proboscidea \"Volcanium\"");
+
+        assert!(code.starts_with("pub mod day16 {"));
+        assert!(code.contains("    const YEAR: u16 = 2022;\n    const DAY: usize = 16;"));
+        assert!(code.contains("        const DAY: usize = 16;"));
+        assert!(code.contains("pub struct Day16;"));
+        assert!(code.contains("impl Solution for Day16 {"));
+        assert!(code.contains(r#""Proboscidea \"Volcanium\"""#));
+        assert!(!code.contains("Template"));
+        assert!(!code.contains("DAY: usize = 0"));
+    }
+
+    #[test]
+    fn keeps_the_title_as_it_is() {
+        let code = generate(TEMPLATE, 2022, 16, "The Template Strikes Back");
+
+        assert!(code.contains(r#""The Template Strikes Back""#));
+        assert!(!code.contains("Day16 Strikes"));
+        assert!(code.contains("pub struct Day16;"));
+    }
+
+    #[test]
+    fn registers_in_main() {
+        // day 0 never has a puzzle, so this keeps working as days are added
+        let main = register_module(MAIN, "day00").unwrap();
+        assert!(main.contains("mod day00;\nmod day01;\n"));
+
+        let main = "mod config;\n\nmod day01;\nmod day03;\n\nuse std::fs;\n";
+        let main = register_module(main, "day02").unwrap();
+        assert_eq!(
+            main,
+            "mod config;\n\nmod day01;\nmod day02;\nmod day03;\n\nuse std::fs;\n"
+        );
+
+        let main = register_module(&main, "y2023_day01").unwrap();
+        assert!(main.contains("mod day03;\nmod y2023_day01;\n\n"));
+
+        let err = register_module(&main, "day02").unwrap_err();
+        assert!(matches!(err, AocError::Cache { .. }));
+        assert!(
+            err.to_string().ends_with("main.rs: day02 is already there"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn registers_in_solutions() {
+        let solution = register_solution(SOLUTION, 2022, 0).unwrap();
+        assert!(solution.contains("    use crate::day00::day00::Day00;\n"));
+        assert!(solution.contains("= &[\n        &Day00, &Day01,"));
+
+        let solution = "\
+pub mod solution {
+    use crate::day01::day01::Day01;
+
+    pub static SOLUTIONS: &[&dyn Solution] = &[
+        &Day01,
+    ];
+}
+";
+        let solution = register_solution(solution, 2022, 2).unwrap();
+        assert!(solution.contains(
+            "    use crate::day01::day01::Day01;\n    use crate::day02::day02::Day02;\n"
+        ));
+        assert!(solution.contains("= &[\n        &Day01, &Day02,\n    ];"));
+
+        let solution = register_solution(&solution, 2021, 3).unwrap();
+        assert!(solution.contains("use crate::y2021_day03::y2021_day03::Y2021Day03;"));
+        assert!(solution.contains("= &[\n        &Y2021Day03, &Day01, &Day02,\n    ];"));
+
+        assert!(matches!(
+            register_solution(&solution, 2022, 2),
+            Err(AocError::Cache { .. })
+        ));
+    }
+}
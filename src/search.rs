@@ -0,0 +1,295 @@
+pub mod search {
+    use std::cmp::Reverse;
+    use std::collections::{BinaryHeap, HashMap, VecDeque};
+    use std::hash::Hash;
+
+    /// Something to search through: where each node leads and what it costs
+    /// to get there.
+    pub trait Graph {
+        type Node: Copy + Eq + Hash;
+
+        fn neighbors(&self, node: Self::Node) -> Vec<Self::Node>;
+
+        /// What stepping from `from` to its neighbour `to` costs; 1 unless
+        /// the graph says otherwise. `bfs` doesn't look at it.
+        fn cost(&self, _from: Self::Node, _to: Self::Node) -> usize {
+            1
+        }
+    }
+
+    /// A graph that can also say where each node is reached from, so it can
+    /// be searched backwards with `Reversed`.
+    pub trait ReversibleGraph: Graph {
+        fn predecessors(&self, node: Self::Node) -> Vec<Self::Node>;
+    }
+
+    /// `G` with every edge turned around, for searching from the end back
+    /// to whichever start is nearest.
+    pub struct Reversed<'a, G>(pub &'a G);
+
+    impl<G: ReversibleGraph> Graph for Reversed<'_, G> {
+        type Node = G::Node;
+
+        fn neighbors(&self, node: G::Node) -> Vec<G::Node> {
+            self.0.predecessors(node)
+        }
+
+        fn cost(&self, from: G::Node, to: G::Node) -> usize {
+            self.0.cost(to, from)
+        }
+    }
+
+    /// What a search found: how far every node it reached is from the
+    /// nearest start, and the way back to it.
+    #[derive(Debug, Clone)]
+    pub struct Search<N> {
+        distances: HashMap<N, usize>,
+        previous: HashMap<N, N>,
+        found: Option<N>,
+    }
+
+    impl<N: Copy + Eq + Hash> Search<N> {
+        fn new() -> Search<N> {
+            Search {
+                distances: HashMap::new(),
+                previous: HashMap::new(),
+                found: None,
+            }
+        }
+
+        /// The goal the search stopped at, if it reached one.
+        pub fn found(&self) -> Option<N> {
+            self.found
+        }
+
+        pub fn distance(&self, node: N) -> Option<usize> {
+            self.distances.get(&node).copied()
+        }
+
+        /// From the start `node` was reached from to `node`, both included.
+        pub fn path(&self, node: N) -> Option<Vec<N>> {
+            self.distances.get(&node)?;
+
+            let mut path = vec![node];
+            let mut current = node;
+            while let Some(&previous) = self.previous.get(&current) {
+                path.push(previous);
+                current = previous;
+            }
+            path.reverse();
+
+            Some(path)
+        }
+
+        /// Records `node` at `distance` if that's the best way there yet.
+        fn improve(&mut self, node: N, distance: usize, from: Option<N>) -> bool {
+            if self.distances.get(&node).is_some_and(|&d| d <= distance) {
+                return false;
+            }
+
+            self.distances.insert(node, distance);
+            match from {
+                Some(from) => self.previous.insert(node, from),
+                None => self.previous.remove(&node),
+            };
+            true
+        }
+    }
+
+    /// Breadth first from every node in `starts` at once, counting steps and
+    /// stopping at the first node `goal` accepts. Pass `|_| false` to reach
+    /// everything.
+    pub fn bfs<G: Graph>(
+        graph: &G,
+        starts: impl IntoIterator<Item = G::Node>,
+        mut goal: impl FnMut(G::Node) -> bool,
+    ) -> Search<G::Node> {
+        let mut search = Search::new();
+        let mut queue = VecDeque::new();
+
+        for start in starts {
+            if search.improve(start, 0, None) {
+                queue.push_back(start);
+            }
+        }
+
+        while let Some(node) = queue.pop_front() {
+            if goal(node) {
+                search.found = Some(node);
+                break;
+            }
+
+            let distance = search.distances[&node] + 1;
+            for next in graph.neighbors(node) {
+                if !search.distances.contains_key(&next) {
+                    search.improve(next, distance, Some(node));
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        search
+    }
+
+    /// The cheapest way from any of `starts` to each node, stopping at the
+    /// first node `goal` accepts.
+    #[allow(dead_code)]
+    pub fn dijkstra<G: Graph>(
+        graph: &G,
+        starts: impl IntoIterator<Item = G::Node>,
+        goal: impl FnMut(G::Node) -> bool,
+    ) -> Search<G::Node> {
+        best_first(graph, starts, goal, |_| 0)
+    }
+
+    /// Like `dijkstra` to `goal`, looking first where `heuristic` says the
+    /// goal is closest. The heuristic must never guess more than the real
+    /// cost, or the path found might not be the cheapest.
+    pub fn astar<G: Graph>(
+        graph: &G,
+        starts: impl IntoIterator<Item = G::Node>,
+        goal: G::Node,
+        heuristic: impl Fn(G::Node) -> usize,
+    ) -> Search<G::Node> {
+        best_first(graph, starts, |node| node == goal, heuristic)
+    }
+
+    fn best_first<G: Graph>(
+        graph: &G,
+        starts: impl IntoIterator<Item = G::Node>,
+        mut goal: impl FnMut(G::Node) -> bool,
+        heuristic: impl Fn(G::Node) -> usize,
+    ) -> Search<G::Node> {
+        let mut search = Search::new();
+        // the heap holds indexes into `nodes`, so nodes don't need an order
+        let mut nodes = vec![];
+        let mut heap = BinaryHeap::new();
+
+        for start in starts {
+            if search.improve(start, 0, None) {
+                heap.push(Reverse((heuristic(start), 0, nodes.len())));
+                nodes.push(start);
+            }
+        }
+
+        while let Some(Reverse((_, distance, i))) = heap.pop() {
+            let node = nodes[i];
+            if distance > search.distances[&node] {
+                // already got here a cheaper way
+                continue;
+            }
+
+            if goal(node) {
+                search.found = Some(node);
+                break;
+            }
+
+            for next in graph.neighbors(node) {
+                let next_distance = distance + graph.cost(node, next);
+                if search.improve(next, next_distance, Some(node)) {
+                    heap.push(Reverse((
+                        next_distance + heuristic(next),
+                        next_distance,
+                        nodes.len(),
+                    )));
+                    nodes.push(next);
+                }
+            }
+        }
+
+        search
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::search::*;
+
+    /// A line of towns 0 to 9 with a road from each to the next, costing
+    /// the town's number, and a toll road from 0 straight to 9.
+    struct Road;
+
+    impl Graph for Road {
+        type Node = u8;
+
+        fn neighbors(&self, node: u8) -> Vec<u8> {
+            match node {
+                0 => vec![1, 9],
+                9 => vec![],
+                n => vec![n + 1],
+            }
+        }
+
+        fn cost(&self, from: u8, to: u8) -> usize {
+            match (from, to) {
+                (0, 9) => 30,
+                _ => from as usize,
+            }
+        }
+    }
+
+    impl ReversibleGraph for Road {
+        fn predecessors(&self, node: u8) -> Vec<u8> {
+            match node {
+                0 => vec![],
+                9 => vec![8, 0],
+                n => vec![n - 1],
+            }
+        }
+    }
+
+    #[test]
+    fn breadth_first_counts_steps() {
+        let search = bfs(&Road, [0], |_| false);
+
+        assert_eq!(search.found(), None);
+        assert_eq!(search.distance(9), Some(1));
+        assert_eq!(search.distance(8), Some(8));
+        assert_eq!(search.path(9), Some(vec![0, 9]));
+        assert!((0..=9).all(|n| search.distance(n).is_some()));
+
+        let search = bfs(&Road, [3, 6], |n| n == 8);
+        assert_eq!(search.found(), Some(8));
+        assert_eq!(search.path(8), Some(vec![6, 7, 8]));
+        assert_eq!(search.distance(0), None);
+        assert_eq!(search.path(0), None);
+    }
+
+    #[test]
+    fn cheapest_first() {
+        // 0+1+...+8 = 36 along the road, 30 on the toll road
+        let search = dijkstra(&Road, [0], |n| n == 9);
+        assert_eq!(search.found(), Some(9));
+        assert_eq!(search.distance(9), Some(30));
+        assert_eq!(search.path(9), Some(vec![0, 9]));
+
+        let search = dijkstra(&Road, [5], |_| false);
+        assert_eq!(search.distance(9), Some(5 + 6 + 7 + 8));
+        assert_eq!(search.path(9), Some(vec![5, 6, 7, 8, 9]));
+
+        let search = astar(&Road, [0], 9, |n| if n == 9 { 0 } else { 1 });
+        assert_eq!(search.distance(9), Some(30));
+    }
+
+    #[test]
+    fn nearest_first() {
+        // breadth first would look at the toll road's end second
+        let mut order = vec![];
+        dijkstra(&Road, [0], |n| {
+            order.push(n);
+            false
+        });
+        assert_eq!(order, (0..=9).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn backwards() {
+        let search = bfs(&Reversed(&Road), [9], |n| n == 0);
+        assert_eq!(search.path(0), Some(vec![9, 0]));
+
+        // costs are those of the roads the right way round
+        let search = dijkstra(&Reversed(&Road), [9], |_| false);
+        assert_eq!(search.distance(0), Some(30));
+        assert_eq!(search.distance(7), Some(7 + 8));
+    }
+}
@@ -0,0 +1,161 @@
+pub mod solution {
+    use std::fmt::{self, Display, Formatter};
+
+    use crate::day01::day01::Day01;
+    use crate::day02::day02::Day02;
+    use crate::day03::day03::Day03;
+    use crate::day04::day04::Day04;
+    use crate::day05::day05::Day05;
+    use crate::day06::day06::Day06;
+    use crate::day07::day07::Day07;
+    use crate::day08::day08::Day08;
+    use crate::day09::day09::Day09;
+    use crate::day10::day10::Day10;
+    use crate::day11::day11::Day11;
+    use crate::day12::day12::Day12;
+    use crate::day13::day13::Day13;
+    use crate::day14::day14::Day14;
+    use crate::day15::day15::Day15;
+    use crate::error::error::AocError;
+
+    /// The result of a single part. Puzzles answer with either a number or
+    /// some text (day 05 stacks, day 10 CRT output).
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Answer {
+        Number(i64),
+        Text(String),
+    }
+
+    impl Display for Answer {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            match self {
+                Answer::Number(n) => write!(f, "{}", n),
+                Answer::Text(s) => write!(f, "{}", s),
+            }
+        }
+    }
+
+    macro_rules! answer_from_number {
+        ($($t:ty),*) => {
+            $(
+                impl From<$t> for Answer {
+                    fn from(value: $t) -> Self {
+                        Answer::Number(value as i64)
+                    }
+                }
+            )*
+        };
+    }
+
+    answer_from_number!(i32, i64, isize, usize);
+
+    impl From<String> for Answer {
+        fn from(value: String) -> Self {
+            Answer::Text(value)
+        }
+    }
+
+    pub trait Solution: Sync {
+        /// The event the puzzle is from, e.g. 2022.
+        fn year(&self) -> u16;
+
+        fn day(&self) -> usize;
+
+        fn title(&self) -> &'static str;
+
+        fn part1(&self, text: String) -> Result<Answer, AocError>;
+
+        fn part2(&self, text: String) -> Result<Answer, AocError>;
+
+        /// Only the input parsing, for days that have it as a separate step,
+        /// so benchmarks can tell it apart from solving.
+        fn parse(&self, _text: &str) -> Option<Result<(), AocError>> {
+            None
+        }
+
+        fn part(&self, part: u8, text: String) -> Result<Answer, AocError> {
+            match part {
+                1 => self.part1(text),
+                2 => self.part2(text),
+                _ => Err(AocError::no_solution(
+                    self.day(),
+                    format!("there is no part {}", part),
+                )),
+            }
+        }
+    }
+
+    /// Every implemented day, in order of year and then day. New days need
+    /// to be added here.
+    pub static SOLUTIONS: &[&dyn Solution] = &[
+        &Day01, &Day02, &Day03, &Day04, &Day05, &Day06, &Day07, &Day08, &Day09, &Day10, &Day11,
+        &Day12, &Day13, &Day14, &Day15,
+    ];
+
+    pub fn find(year: u16, day: usize) -> Option<&'static dyn Solution> {
+        SOLUTIONS
+            .iter()
+            .find(|s| s.year() == year && s.day() == day)
+            .copied()
+    }
+
+    /// Every implemented day of `year`.
+    pub fn solutions(year: u16) -> Vec<&'static dyn Solution> {
+        SOLUTIONS
+            .iter()
+            .filter(|s| s.year() == year)
+            .copied()
+            .collect()
+    }
+
+    /// The most recent year with any implemented days, used when no year is
+    /// given.
+    pub fn latest_year() -> u16 {
+        SOLUTIONS
+            .iter()
+            .map(|s| s.year())
+            .max()
+            .expect("at least one day is implemented")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::solution::*;
+
+    #[test]
+    fn registry_is_ordered_and_complete() {
+        let keys: Vec<(u16, usize)> = SOLUTIONS.iter().map(|s| (s.year(), s.day())).collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(
+            keys, sorted,
+            "the registry is out of order or has duplicates"
+        );
+
+        for (i, s) in solutions(2022).iter().enumerate() {
+            assert_eq!(s.day(), i + 1, "{} is out of order", s.title());
+            assert!(!s.title().is_empty(), "day {} has no title", s.day());
+        }
+    }
+
+    #[test]
+    fn find_day() {
+        assert_eq!(
+            find(2022, 14).map(|s| s.title()),
+            Some("Regolith Reservoir")
+        );
+        assert!(find(2022, 0).is_none());
+        assert!(find(2022, 26).is_none());
+        assert!(find(2021, 14).is_none());
+        assert_eq!(latest_year(), 2022);
+    }
+
+    #[test]
+    fn answer_display() {
+        assert_eq!(Answer::from(42usize).to_string(), "42");
+        assert_eq!(Answer::from(-7isize).to_string(), "-7");
+        assert_eq!(Answer::from("CMZ".to_string()).to_string(), "CMZ");
+    }
+}
@@ -0,0 +1,230 @@
+pub mod sparse {
+    use std::collections::HashMap;
+
+    use crate::point::point::Point;
+
+    /// Anywhere, with `y` growing downwards.
+    pub type Pos = Point<isize>;
+
+    /// How many cells wide and high a chunk is.
+    const CHUNK: isize = 16;
+
+    /// A square of cells stored together, so neighbours are usually one
+    /// lookup away.
+    #[derive(Debug, Clone)]
+    struct Chunk<T> {
+        cells: Vec<Option<T>>,
+        occupied: usize,
+    }
+
+    impl<T> Chunk<T> {
+        fn new() -> Chunk<T> {
+            Chunk {
+                cells: (0..CHUNK * CHUNK).map(|_| None).collect(),
+                occupied: 0,
+            }
+        }
+    }
+
+    /// Which chunk `pos` is in, and where in it.
+    fn split(pos: Pos) -> (Pos, usize) {
+        let key = pos.map(|v| v.div_euclid(CHUNK));
+        let index = pos.y.rem_euclid(CHUNK) * CHUNK + pos.x.rem_euclid(CHUNK);
+        (key, index as usize)
+    }
+
+    fn join(key: Pos, index: usize) -> Pos {
+        let index = index as isize;
+        key * CHUNK + Point::new(index % CHUNK, index / CHUNK)
+    }
+
+    /// A grid without edges. Only the cells that were set are stored, every
+    /// other one reads as `default`, and the box around everything set so
+    /// far is kept up to date.
+    #[derive(Debug, Clone)]
+    pub struct SparseGrid<T> {
+        chunks: HashMap<Pos, Chunk<T>>,
+        default: T,
+        bounds: Option<(Pos, Pos)>,
+        len: usize,
+    }
+
+    impl<T: Default> Default for SparseGrid<T> {
+        fn default() -> SparseGrid<T> {
+            SparseGrid::new(T::default())
+        }
+    }
+
+    impl<T> SparseGrid<T> {
+        pub fn new(default: T) -> SparseGrid<T> {
+            SparseGrid {
+                chunks: HashMap::new(),
+                default,
+                bounds: None,
+                len: 0,
+            }
+        }
+
+        /// The cell at `pos`, or the default if it was never set.
+        pub fn get(&self, pos: Pos) -> &T {
+            self.occupied(pos).unwrap_or(&self.default)
+        }
+
+        /// The cell at `pos` if it was set.
+        pub fn occupied(&self, pos: Pos) -> Option<&T> {
+            let (key, index) = split(pos);
+            self.chunks.get(&key)?.cells[index].as_ref()
+        }
+
+        /// Puts `value` at `pos`, giving back what was set there before.
+        pub fn set(&mut self, pos: Pos, value: T) -> Option<T> {
+            let (key, index) = split(pos);
+            let chunk = self.chunks.entry(key).or_insert_with(Chunk::new);
+            let previous = chunk.cells[index].replace(value);
+
+            if previous.is_none() {
+                chunk.occupied += 1;
+                self.len += 1;
+            }
+
+            self.bounds = Some(match self.bounds {
+                Some((min, max)) => (
+                    Point::new(min.x.min(pos.x), min.y.min(pos.y)),
+                    Point::new(max.x.max(pos.x), max.y.max(pos.y)),
+                ),
+                None => (pos, pos),
+            });
+
+            previous
+        }
+
+        /// Unsets `pos`, so it reads as the default again. The bounds stay
+        /// as they were.
+        #[allow(dead_code)]
+        pub fn remove(&mut self, pos: Pos) -> Option<T> {
+            let (key, index) = split(pos);
+            let chunk = self.chunks.get_mut(&key)?;
+            let previous = chunk.cells[index].take()?;
+
+            chunk.occupied -= 1;
+            self.len -= 1;
+            if chunk.occupied == 0 {
+                self.chunks.remove(&key);
+            }
+
+            Some(previous)
+        }
+
+        /// How many cells are set.
+        pub fn len(&self) -> usize {
+            self.len
+        }
+
+        #[allow(dead_code)]
+        pub fn is_empty(&self) -> bool {
+            self.len == 0
+        }
+
+        /// The top left and bottom right corners of everything ever set.
+        pub fn bounds(&self) -> Option<(Pos, Pos)> {
+            self.bounds
+        }
+
+        /// Every cell that's set, in no particular order.
+        pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+            self.chunks.iter().flat_map(|(&key, chunk)| {
+                chunk
+                    .cells
+                    .iter()
+                    .enumerate()
+                    .filter_map(move |(i, cell)| cell.as_ref().map(|cell| (join(key, i), cell)))
+            })
+        }
+
+        /// The cells from `min` to `max`, both included, drawn a character
+        /// per cell and a line per row.
+        pub fn render(&self, min: Pos, max: Pos, mut f: impl FnMut(&T) -> char) -> String {
+            let lines: Vec<String> = (min.y..=max.y)
+                .map(|y| {
+                    (min.x..=max.x)
+                        .map(|x| f(self.get(Point::new(x, y))))
+                        .collect()
+                })
+                .collect();
+            lines.join("\n")
+        }
+
+        /// Everything within the bounds drawn with `render`.
+        pub fn render_all(&self, f: impl FnMut(&T) -> char) -> String {
+            match self.bounds {
+                Some((min, max)) => self.render(min, max, f),
+                None => String::new(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::sparse::*;
+    use crate::point::point::Point;
+
+    fn at(x: isize, y: isize) -> Pos {
+        Point::new(x, y)
+    }
+
+    #[test]
+    fn reads_the_default_until_set() {
+        let mut grid = SparseGrid::new('.');
+
+        assert_eq!(grid.get(at(-100, 7)), &'.');
+        assert_eq!(grid.set(at(-100, 7), '#'), None);
+        assert_eq!(grid.set(at(-100, 7), 'O'), Some('#'));
+        assert_eq!(grid.get(at(-100, 7)), &'O');
+        assert_eq!(grid.occupied(at(-99, 7)), None);
+        assert_eq!(grid.len(), 1);
+
+        assert_eq!(grid.remove(at(-100, 7)), Some('O'));
+        assert_eq!(grid.remove(at(-100, 7)), None);
+        assert!(grid.is_empty());
+        assert_eq!(grid.get(at(-100, 7)), &'.');
+    }
+
+    #[test]
+    fn tracks_bounds_and_renders() {
+        let mut grid: SparseGrid<bool> = SparseGrid::default();
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.render_all(|_| '#'), "");
+
+        grid.set(at(2, -1), true);
+        grid.set(at(-1, 1), true);
+        grid.set(at(1_000_000, 0), false);
+        grid.remove(at(1_000_000, 0));
+
+        assert_eq!(grid.bounds(), Some((at(-1, -1), at(1_000_000, 1))));
+        assert_eq!(
+            grid.render(at(-1, -1), at(2, 1), |&b| if b { '#' } else { '.' }),
+            "...#\n....\n#..."
+        );
+    }
+
+    #[test]
+    fn iterates_over_what_is_set() {
+        let mut grid = SparseGrid::new(0);
+        let cells = [at(0, 0), at(15, 15), at(16, 0), at(-1, -1), at(-17, 40)];
+        for (i, &pos) in cells.iter().enumerate() {
+            grid.set(pos, i + 1);
+        }
+
+        let mut set: Vec<(Pos, usize)> = grid.iter().map(|(pos, &v)| (pos, v)).collect();
+        set.sort();
+        let mut expected: Vec<(Pos, usize)> = cells
+            .iter()
+            .enumerate()
+            .map(|(i, &pos)| (pos, i + 1))
+            .collect();
+        expected.sort();
+
+        assert_eq!(set, expected);
+    }
+}
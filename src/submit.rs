@@ -0,0 +1,422 @@
+pub mod submit {
+    use std::fmt;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::client::client::Client;
+    use crate::config::config::{Config, TokenSource};
+    use crate::error::error::AocError;
+    use crate::helper::helper::AOC_URL;
+    use crate::solution::solution::Answer;
+
+    const HISTORY_FILE: &str = "submissions.json";
+
+    /// What the site said about a submitted answer.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    pub enum Outcome {
+        Correct,
+        /// wrong, without a hint about which direction
+        Incorrect,
+        TooHigh,
+        TooLow,
+        /// submitted too soon after the last attempt, nothing was checked
+        Wait(Duration),
+        /// the part was already solved, nothing was checked
+        AlreadySolved,
+        /// a page we don't know how to read, kept verbatim
+        Unknown(String),
+    }
+
+    impl fmt::Display for Outcome {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Outcome::Correct => write!(f, "correct"),
+                Outcome::Incorrect => write!(f, "incorrect"),
+                Outcome::TooHigh => write!(f, "too high"),
+                Outcome::TooLow => write!(f, "too low"),
+                Outcome::Wait(d) => write!(f, "wait {}s before trying again", d.as_secs()),
+                Outcome::AlreadySolved => write!(f, "already solved"),
+                Outcome::Unknown(text) => write!(f, "unrecognised response: {}", text),
+            }
+        }
+    }
+
+    impl Outcome {
+        /// Whether the site actually judged the answer.
+        fn judged(&self) -> bool {
+            matches!(
+                self,
+                Outcome::Correct | Outcome::Incorrect | Outcome::TooHigh | Outcome::TooLow
+            )
+        }
+    }
+
+    /// Strips tags from the `<article>` of a response page.
+    fn article_text(html: &str) -> String {
+        let start = html.find("<article").unwrap_or(0);
+        let end = html[start..]
+            .find("</article>")
+            .map_or(html.len(), |i| start + i);
+
+        let mut text = String::new();
+        let mut in_tag = false;
+        for c in html[start..end].chars() {
+            match c {
+                '<' => in_tag = true,
+                '>' => in_tag = false,
+                c if !in_tag => text.push(c),
+                _ => {}
+            }
+        }
+
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// Reads "You have 1m 25s left to wait" style durations.
+    fn parse_wait(text: &str) -> Option<Duration> {
+        let start = text.find("You have ")? + "You have ".len();
+        let end = start + text[start..].find(" left to wait")?;
+
+        let mut secs = 0;
+        for token in text[start..end].split_whitespace() {
+            let (i, _) = token.char_indices().last()?;
+            let (n, unit) = token.split_at(i);
+            let n: u64 = n.parse().ok()?;
+            secs += match unit {
+                "h" => n * 3600,
+                "m" => n * 60,
+                "s" => n,
+                _ => return None,
+            };
+        }
+
+        Some(Duration::from_secs(secs))
+    }
+
+    pub fn parse_response(html: &str) -> Outcome {
+        let text = article_text(html);
+
+        if text.contains("That's the right answer") {
+            Outcome::Correct
+        } else if text.contains("That's not the right answer") {
+            if text.contains("your answer is too high") {
+                Outcome::TooHigh
+            } else if text.contains("your answer is too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Incorrect
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Outcome::Wait(parse_wait(&text).unwrap_or_default())
+        } else if text.contains("You don't seem to be solving the right level") {
+            Outcome::AlreadySolved
+        } else {
+            Outcome::Unknown(text)
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct Attempt {
+        pub day: usize,
+        pub part: u8,
+        pub answer: String,
+        pub outcome: Outcome,
+        /// seconds since the unix epoch
+        pub at: u64,
+    }
+
+    /// Every answer ever submitted, so we can refuse to repeat a mistake.
+    #[derive(Debug, Default, Serialize, Deserialize)]
+    pub struct History {
+        pub attempts: Vec<Attempt>,
+    }
+
+    impl History {
+        pub fn load(path: &PathBuf) -> Result<History, AocError> {
+            if !path.exists() {
+                return Ok(History::default());
+            }
+            let text = fs::read_to_string(path).map_err(|e| AocError::cache(path, e))?;
+            serde_json::from_str(&text).map_err(|e| AocError::cache(path, e))
+        }
+
+        pub fn save(&self, path: &PathBuf) -> Result<(), AocError> {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).map_err(|e| AocError::cache(dir, e))?;
+            }
+            let text = serde_json::to_string_pretty(self).map_err(|e| AocError::cache(path, e))?;
+            fs::write(path, text).map_err(|e| AocError::cache(path, e))
+        }
+
+        fn judged(&self, day: usize, part: u8) -> impl Iterator<Item = &Attempt> {
+            self.attempts
+                .iter()
+                .filter(move |a| a.day == day && a.part == part && a.outcome.judged())
+        }
+
+        /// Explains why `answer` is already known to be right or wrong, if it is.
+        pub fn check(&self, day: usize, part: u8, answer: &Answer) -> Option<String> {
+            let text = answer.to_string();
+
+            if let Some(a) = self.judged(day, part).find(|a| a.answer == text) {
+                return Some(format!("{} was already submitted: {}", text, a.outcome));
+            }
+
+            if let Some(a) = self
+                .judged(day, part)
+                .find(|a| a.outcome == Outcome::Correct)
+            {
+                return Some(format!("already solved with {}", a.answer));
+            }
+
+            let n = match answer {
+                Answer::Number(n) => *n,
+                Answer::Text(_) => return None,
+            };
+
+            let bound = |outcome: Outcome| {
+                self.judged(day, part)
+                    .filter(move |a| a.outcome == outcome)
+                    .flat_map(|a| a.answer.parse::<i64>().ok())
+            };
+
+            if let Some(high) = bound(Outcome::TooHigh).min().filter(|&high| n >= high) {
+                return Some(format!("{} is at least {}, which was too high", n, high));
+            }
+
+            if let Some(low) = bound(Outcome::TooLow).max().filter(|&low| n <= low) {
+                return Some(format!("{} is at most {}, which was too low", n, low));
+            }
+
+            None
+        }
+    }
+
+    /// Submits answers for the puzzles of one year, keeping the history
+    /// in `.advent/{year}/submissions.json`.
+    pub struct Submitter {
+        client: Client,
+        base_url: String,
+        token: TokenSource,
+        year: u16,
+        history: PathBuf,
+    }
+
+    impl Submitter {
+        pub fn new(config: &Config, year: u16) -> Submitter {
+            Submitter {
+                client: Client::new(config),
+                base_url: AOC_URL.to_string(),
+                token: config.token().clone(),
+                year,
+                history: config.cache_dir().join(year.to_string()).join(HISTORY_FILE),
+            }
+        }
+
+        pub fn with_base_url<S: Into<String>>(mut self, base_url: S) -> Submitter {
+            self.base_url = base_url.into();
+            self
+        }
+
+        #[cfg(test)]
+        pub fn with_token<S: Into<String>>(mut self, token: S) -> Submitter {
+            self.token = TokenSource::Value(token.into());
+            self
+        }
+
+        #[cfg(test)]
+        pub fn with_client(mut self, client: Client) -> Submitter {
+            self.client = client;
+            self
+        }
+
+        #[cfg(test)]
+        pub fn with_history<P: Into<PathBuf>>(mut self, history: P) -> Submitter {
+            self.history = history.into();
+            self
+        }
+
+        fn post(&self, day: usize, part: u8, answer: &str) -> Result<String, AocError> {
+            let token = self.token.read().map_err(|e| {
+                AocError::unavailable(day, format!("no session token in {}: {}", self.token, e))
+            })?;
+
+            let url = format!("{}/{}/day/{}/answer", self.base_url, self.year, day);
+            let form = [("level", part.to_string()), ("answer", answer.to_string())];
+            self.client
+                .post_form(&url, &token, &form)
+                .map(|page| page.body)
+        }
+
+        /// Submits `answer` unless the history rules it out, which fails
+        /// with `AocError::Rejected`.
+        pub fn submit(&self, day: usize, part: u8, answer: &Answer) -> Result<Outcome, AocError> {
+            let mut history = History::load(&self.history)?;
+
+            if let Some(reason) = history.check(day, part, answer) {
+                return Err(AocError::rejected(reason));
+            }
+
+            let text = answer.to_string();
+            let outcome = parse_response(&self.post(day, part, &text)?);
+
+            history.attempts.push(Attempt {
+                day,
+                part,
+                answer: text,
+                outcome: outcome.clone(),
+                at: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+            });
+            history.save(&self.history)?;
+
+            Ok(outcome)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::submit::*;
+    use crate::client::client::Client;
+    use crate::config::config::Config;
+    use crate::error::error::AocError;
+    use crate::solution::solution::Answer;
+    use crate::testing::testing::{temp_dir, MockServer};
+
+    fn page(article: &str) -> String {
+        format!(
+            "<html><body><main><article><p>{}</p></article></main></body></html>",
+            article
+        )
+    }
+
+    #[test]
+    fn parse_responses() {
+        let tests = [
+            (
+                "That's the right answer! You are one gold star closer.",
+                Outcome::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high. Please wait one minute.",
+                Outcome::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Outcome::TooLow,
+            ),
+            (
+                "That's not the right answer. If you're stuck, make sure you're using the full input data.",
+                Outcome::Incorrect,
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 25s left to wait.",
+                Outcome::Wait(Duration::from_secs(85)),
+            ),
+            (
+                "You gave an answer too recently. You have 42s left to wait.",
+                Outcome::Wait(Duration::from_secs(42)),
+            ),
+            (
+                // whatever the unit is, it isn't one of ours
+                "You gave an answer too recently. You have 5é left to wait.",
+                Outcome::Wait(Duration::ZERO),
+            ),
+            (
+                "You don't seem to be solving the right level. Did you already complete it?",
+                Outcome::AlreadySolved,
+            ),
+        ];
+
+        for (article, expected) in tests {
+            assert_eq!(parse_response(&page(article)), expected, "{}", article);
+        }
+    }
+
+    #[test]
+    fn parse_strips_markup() {
+        let html = page("That's <em>not</em> the right answer; your answer is too <em>low</em>.");
+        assert_eq!(parse_response(&html), Outcome::TooLow);
+
+        let html = "<html><p>Something else entirely</p></html>";
+        assert_eq!(
+            parse_response(html),
+            Outcome::Unknown("Something else entirely".to_string())
+        );
+    }
+
+    #[test]
+    fn history_bounds() {
+        let mut history = History::default();
+        let attempt = |answer: &str, outcome| Attempt {
+            day: 1,
+            part: 1,
+            answer: answer.to_string(),
+            outcome,
+            at: 0,
+        };
+        history.attempts.push(attempt("100", Outcome::TooHigh));
+        history.attempts.push(attempt("10", Outcome::TooLow));
+        history
+            .attempts
+            .push(attempt("50", Outcome::Wait(Duration::from_secs(5))));
+        history.attempts.push(attempt("42", Outcome::Incorrect));
+
+        assert!(history.check(1, 1, &Answer::Number(150)).is_some());
+        assert!(history.check(1, 1, &Answer::Number(100)).is_some());
+        assert!(history.check(1, 1, &Answer::Number(5)).is_some());
+        assert!(history.check(1, 1, &Answer::Number(42)).is_some());
+        // a wait means the answer was never checked
+        assert!(history.check(1, 1, &Answer::Number(50)).is_none());
+        assert!(history.check(1, 1, &Answer::Number(11)).is_none());
+        // bounds belong to a single part
+        assert!(history.check(1, 2, &Answer::Number(150)).is_none());
+
+        history.attempts.push(attempt("77", Outcome::Correct));
+        assert!(history.check(1, 1, &Answer::Number(60)).is_some());
+    }
+
+    #[test]
+    fn submit_against_mock_server() {
+        let server = MockServer::start();
+        server.respond(
+            "/2022/day/3/answer",
+            200,
+            &page("That's not the right answer; your answer is too low."),
+        );
+
+        let dir = temp_dir("submit");
+        let config = Config::default();
+        let submitter = Submitter::new(&config, 2022)
+            .with_client(Client::new(&config).without_delays())
+            .with_base_url(server.url())
+            .with_token("secret")
+            .with_history(dir.join("submissions.json"));
+
+        let outcome = submitter.submit(3, 2, &Answer::Number(42)).unwrap();
+        assert_eq!(outcome, Outcome::TooLow);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].header("cookie"), Some("session=secret"));
+        assert_eq!(requests[0].body, "level=2&answer=42");
+
+        // known to be too low, so the server is never asked again
+        let err = submitter.submit(3, 2, &Answer::Number(40)).unwrap_err();
+        assert!(matches!(err, AocError::Rejected { .. }), "{}", err);
+        assert_eq!(server.requests().len(), 1);
+
+        let history = History::load(&dir.join("submissions.json")).unwrap();
+        assert_eq!(history.attempts.len(), 1);
+        assert_eq!(history.attempts[0].answer, "42");
+    }
+}
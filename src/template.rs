@@ -1,38 +1,71 @@
-#[cfg(test)]
+// The starting point for a new day: `new --day N` copies this to
+// `src/dayNN.rs` with the module, year, day and title filled in.
 pub mod template {
-    pub fn part1(_text: String) -> Result<isize, Box<dyn std::error::Error>> {
+    use crate::error::error::AocError;
+    use crate::solution::solution::{Answer, Solution};
+
+    const YEAR: u16 = 2022;
+    const DAY: usize = 0;
+
+    pub fn part1(_text: String) -> Result<isize, AocError> {
         todo!("need to do it");
     }
 
-    pub fn part2(_text: String) -> Result<isize, Box<dyn std::error::Error>> {
+    pub fn part2(_text: String) -> Result<isize, AocError> {
         todo!("not doing it");
     }
 
+    pub struct Template;
+
+    impl Solution for Template {
+        fn year(&self) -> u16 {
+            YEAR
+        }
+
+        fn day(&self) -> usize {
+            DAY
+        }
+
+        fn title(&self) -> &'static str {
+            "Template"
+        }
+
+        fn part1(&self, text: String) -> Result<Answer, AocError> {
+            part1(text).map(Answer::from)
+        }
+
+        fn part2(&self, text: String) -> Result<Answer, AocError> {
+            part2(text).map(Answer::from)
+        }
+    }
+
+    #[cfg(test)]
     mod test {
         use crate::helper::helper::{dbg_day, run_day};
 
         use super::*;
 
-        const DAY: usize = 15;
+        const YEAR: u16 = 2022;
+        const DAY: usize = 0;
 
         #[test]
         fn test_part1() {
-            run_day(DAY, part1);
+            run_day(YEAR, DAY, 1, part1);
         }
 
         #[test]
         fn test_part2() {
-            run_day(DAY, part2);
+            run_day(YEAR, DAY, 2, part2);
         }
 
         #[test]
         fn dbg_part1() {
-            dbg_day(DAY, part1);
+            dbg_day(YEAR, DAY, part1);
         }
 
         #[test]
         fn dbg_part2() {
-            dbg_day(DAY, part2);
+            dbg_day(YEAR, DAY, part2);
         }
     }
 }
@@ -0,0 +1,150 @@
+#[cfg(test)]
+pub mod testing {
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::{env, fs, process, thread};
+
+    /// A fresh, empty directory under the system temp dir.
+    pub fn temp_dir(name: &str) -> PathBuf {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let n = COUNTER.fetch_add(1, Ordering::SeqCst);
+        let dir = env::temp_dir().join(format!("advent-2022-{}-{}-{}", process::id(), name, n));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct Request {
+        pub method: String,
+        pub path: String,
+        pub headers: Vec<(String, String)>,
+        pub body: String,
+    }
+
+    impl Request {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.as_str())
+        }
+    }
+
+    #[derive(Default)]
+    struct State {
+        routes: HashMap<String, Vec<(u16, String)>>,
+        requests: Vec<Request>,
+    }
+
+    /// A tiny HTTP/1.1 server on localhost standing in for adventofcode.com.
+    /// Responses are queued per path; the last response for a path repeats.
+    pub struct MockServer {
+        addr: String,
+        state: Arc<Mutex<State>>,
+    }
+
+    impl MockServer {
+        pub fn start() -> MockServer {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let addr = listener.local_addr().unwrap().to_string();
+            let state = Arc::new(Mutex::new(State::default()));
+
+            let shared = Arc::clone(&state);
+            thread::spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    handle(stream, &shared);
+                }
+            });
+
+            MockServer { addr, state }
+        }
+
+        pub fn url(&self) -> String {
+            format!("http://{}", self.addr)
+        }
+
+        pub fn respond(&self, path: &str, status: u16, body: &str) {
+            self.state
+                .lock()
+                .unwrap()
+                .routes
+                .entry(path.to_string())
+                .or_default()
+                .push((status, body.to_string()));
+        }
+
+        pub fn requests(&self) -> Vec<Request> {
+            self.state.lock().unwrap().requests.clone()
+        }
+    }
+
+    fn read_request(stream: &TcpStream) -> Option<Request> {
+        let mut reader = BufReader::new(stream);
+
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let mut parts = line.split_whitespace();
+        let method = parts.next()?.to_string();
+        let path = parts.next()?.to_string();
+
+        let mut headers = vec![];
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).ok()?;
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            let (k, v) = line.split_once(':')?;
+            headers.push((k.trim().to_string(), v.trim().to_string()));
+        }
+
+        let length = headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, v)| v.parse().ok())
+            .unwrap_or(0);
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).ok()?;
+
+        Some(Request {
+            method,
+            path,
+            headers,
+            body: String::from_utf8_lossy(&body).to_string(),
+        })
+    }
+
+    fn handle(mut stream: TcpStream, state: &Mutex<State>) {
+        let request = match read_request(&stream) {
+            Some(request) => request,
+            None => return,
+        };
+
+        let (status, body) = {
+            let mut state = state.lock().unwrap();
+            let path = request.path.split('?').next().unwrap_or("").to_string();
+            state.requests.push(request);
+
+            match state.routes.get_mut(&path) {
+                Some(queue) if queue.len() > 1 => queue.remove(0),
+                Some(queue) if !queue.is_empty() => queue[0].clone(),
+                _ => (404, "Not Found".to_string()),
+            }
+        };
+
+        let response = format!(
+            "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nContent-Type: text/plain\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        let _ = stream.write_all(response.as_bytes());
+    }
+}
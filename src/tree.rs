@@ -12,7 +12,7 @@ pub mod tree {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             let mut s = f.debug_struct("Tree");
             s.field("data", &self.data);
-            if self.children.len() > 0 {
+            if !self.children.is_empty() {
                 s.field("children", &self.children);
             }
             s.finish()
@@ -24,7 +24,7 @@ pub mod tree {
             Rc::new(RefCell::new(Tree {
                 parent: None,
                 children: vec![],
-                data: data,
+                data,
             }))
         }
 
@@ -32,7 +32,7 @@ pub mod tree {
             let child = Rc::new(RefCell::new(Tree {
                 parent: Some(Rc::clone(parent)),
                 children: vec![],
-                data: data,
+                data,
             }));
 
             let mut real_parent = parent.as_ref().borrow_mut();
@@ -0,0 +1,153 @@
+pub mod unlock {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use crate::error::error::AocError;
+
+    /// Puzzles unlock at midnight US-Eastern, which the event keeps at
+    /// UTC-5 throughout December.
+    const UNLOCK_HOUR_UTC: u64 = 5;
+
+    /// The last day with a puzzle: 25 until 2024, 12 since 2025.
+    pub fn last_day(year: u16) -> usize {
+        if year >= 2025 {
+            12
+        } else {
+            25
+        }
+    }
+
+    /// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+    fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = month as i64;
+        let day_of_year =
+            (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// When the puzzle for `day` of `year` unlocks, if there is one.
+    pub fn unlock_time(year: u16, day: usize) -> Option<SystemTime> {
+        if !(1..=last_day(year)).contains(&day) {
+            return None;
+        }
+
+        let days = days_from_civil(year as i64, 12, day as u32);
+        let secs = days as u64 * 86_400 + UNLOCK_HOUR_UTC * 3600;
+        Some(UNIX_EPOCH + Duration::from_secs(secs))
+    }
+
+    /// How long until `day` unlocks, or `None` once it has.
+    pub fn until_unlock(year: u16, day: usize, now: SystemTime) -> Option<Duration> {
+        unlock_time(year, day)?
+            .duration_since(now)
+            .ok()
+            .filter(|wait| !wait.is_zero())
+    }
+
+    /// The first puzzle of `year` that is still locked at `now`.
+    pub fn next_unlock(year: u16, now: SystemTime) -> Option<usize> {
+        (1..=last_day(year)).find(|&day| until_unlock(year, day, now).is_some())
+    }
+
+    /// Fails for days that don't have a puzzle, or don't have one yet, so
+    /// nothing asks the site for them.
+    pub fn check_unlocked(year: u16, day: usize, now: SystemTime) -> Result<(), AocError> {
+        if unlock_time(year, day).is_none() {
+            return Err(AocError::unavailable(
+                day,
+                format!("{} has no puzzle for day {}", year, day),
+            ));
+        }
+
+        match until_unlock(year, day, now) {
+            Some(wait) => Err(AocError::unavailable(
+                day,
+                format!(
+                    "day {} of {} unlocks in {} ({} 05:00 UTC), use `wait` to wait for it",
+                    day,
+                    year,
+                    format_wait(wait),
+                    date(year, day)
+                ),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn date(year: u16, day: usize) -> String {
+        format!("{}-12-{:02}", year, day)
+    }
+
+    /// `3h 2m 1s`, leaving out leading zeros.
+    pub fn format_wait(wait: Duration) -> String {
+        let secs = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+        let (d, h, m, s) = (secs / 86_400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+
+        let parts = [(d, "d"), (h, "h"), (m, "m"), (s, "s")];
+        let first = parts.iter().position(|&(n, _)| n > 0).unwrap_or(3);
+        parts[first..]
+            .iter()
+            .map(|(n, unit)| format!("{}{}", n, unit))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::unlock::*;
+
+    #[test]
+    fn unlock_times() {
+        // 2022-12-01T05:00:00Z
+        let first = UNIX_EPOCH + Duration::from_secs(1_669_870_800);
+        assert_eq!(unlock_time(2022, 1), Some(first));
+        assert_eq!(
+            unlock_time(2022, 25),
+            Some(first + Duration::from_secs(24 * 86_400))
+        );
+        // 2015-12-01T05:00:00Z
+        assert_eq!(
+            unlock_time(2015, 1),
+            Some(UNIX_EPOCH + Duration::from_secs(1_448_946_000))
+        );
+
+        assert_eq!(unlock_time(2022, 0), None);
+        assert_eq!(unlock_time(2022, 26), None);
+        assert_eq!(unlock_time(2025, 13), None);
+    }
+
+    #[test]
+    fn waiting() {
+        let first = unlock_time(2022, 1).unwrap();
+        let before = first - Duration::from_secs(3 * 3600 + 2 * 60 + 1);
+
+        assert_eq!(
+            until_unlock(2022, 1, before),
+            Some(Duration::from_secs(10_921))
+        );
+        assert_eq!(until_unlock(2022, 1, first), None);
+        assert_eq!(next_unlock(2022, before), Some(1));
+        assert_eq!(next_unlock(2022, first), Some(2));
+        assert_eq!(next_unlock(2022, unlock_time(2022, 25).unwrap()), None);
+
+        let err = check_unlocked(2022, 1, before).unwrap_err().to_string();
+        assert!(err.contains("unlocks in 3h 2m 1s"), "{}", err);
+        assert!(err.contains("2022-12-01 05:00 UTC"), "{}", err);
+        assert!(check_unlocked(2022, 1, first).is_ok());
+        assert!(check_unlocked(2022, 26, first).is_err());
+    }
+
+    #[test]
+    fn formats_waits() {
+        assert_eq!(format_wait(Duration::from_secs(0)), "0s");
+        assert_eq!(format_wait(Duration::from_millis(1500)), "2s");
+        assert_eq!(format_wait(Duration::from_secs(3600)), "1h 0m 0s");
+        assert_eq!(format_wait(Duration::from_secs(90_061)), "1d 1h 1m 1s");
+    }
+}